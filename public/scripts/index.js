@@ -2,7 +2,7 @@ function addPageIdCopyButton() {
     const li = document.querySelector(".breadcrumb_section > ol > li:nth-child(2)");
     if (li === null) return;
     const pageId = li.textContent.trim();
-    if (pageId === "pages" || pageId === "titles") return;
+    if (pageId === "pages" || pageId === "tags" || pageId === "titles") return;
     const button = createClipboardCopyButton(pageId);
     li.appendChild(button);
 }
@@ -42,9 +42,38 @@ function createClipboardCopyButton(text) {
     return button;
 }
 
+// re-fetches the current page and replaces the nav and the content
+async function reloadPage() {
+    const response = await fetch(location.href);
+    if (!response.ok) return;
+    const doc = new DOMParser().parseFromString(await response.text(), "text/html");
+    for (const selector of ["nav", ".content_section"]) {
+        const current = document.querySelector(selector);
+        const next = doc.querySelector(selector);
+        if (current === null || next === null) continue;
+        current.replaceWith(next);
+    }
+    document.title = doc.title;
+    addPageIdCopyButton();
+    addTitleUrlCopyButton();
+}
+
+function watchPageChanges() {
+    const pageId = document.body.dataset.pageId;
+    if (pageId === undefined) return;
+    const eventSource = new EventSource("/events");
+    eventSource.addEventListener("message", async (event) => {
+        const { all, id, links } = JSON.parse(event.data);
+        // the backlinks change when a page linking to this page changes
+        if (!all && id !== pageId && !links.includes(pageId)) return;
+        await reloadPage();
+    });
+}
+
 function main() {
     addPageIdCopyButton();
     addTitleUrlCopyButton();
+    watchPageChanges();
 }
 
 main();
@@ -2,9 +2,43 @@ use anyhow::Context;
 
 #[derive(Clone)]
 pub(crate) struct Config {
+    access_token: Option<String>,
+    bind_address: ConfigBindAddress,
     data_dir: std::path::PathBuf,
     image_sync: Option<ConfigImageSync>,
+    markdown: ConfigMarkdown,
     port: Option<u16>,
+    search: ConfigSearch,
+    warn_duplicate_titles: bool,
+}
+
+/// The address to listen on
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ConfigBindAddress {
+    /// An IPv4 or IPv6 address (with `port`)
+    Ip(std::net::IpAddr),
+    /// A Unix domain socket path
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+impl std::str::FromStr for ConfigBindAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ip_addr) = <std::net::IpAddr as std::str::FromStr>::from_str(s) {
+            return Ok(Self::Ip(ip_addr));
+        }
+        let path = match s.strip_prefix("unix:") {
+            Some(path) => path,
+            None if s.starts_with('/') => s,
+            None => anyhow::bail!("invalid bind_address: {s}"),
+        };
+        #[cfg(unix)]
+        return Ok(Self::Unix(std::path::PathBuf::from(path)));
+        #[cfg(not(unix))]
+        anyhow::bail!("Unix domain sockets are not supported on this platform: {path}");
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -14,6 +48,48 @@ pub(crate) struct ConfigImageSync {
     pub(crate) object_prefix: String,
 }
 
+/// The Markdown extensions to enable and how the title is extracted
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ConfigMarkdown {
+    /// Use the first non-empty line as the title of a page without H1
+    pub(crate) first_line_title: bool,
+    pub(crate) footnotes: bool,
+    pub(crate) heading_attributes: bool,
+    pub(crate) smart_punctuation: bool,
+    pub(crate) strikethrough: bool,
+    pub(crate) tables: bool,
+    pub(crate) tasklists: bool,
+}
+
+impl ConfigMarkdown {
+    pub(crate) fn options(&self) -> pulldown_cmark::Options {
+        // `[[Title]]` links are always enabled
+        let mut options = pulldown_cmark::Options::ENABLE_WIKILINKS;
+        options.set(pulldown_cmark::Options::ENABLE_FOOTNOTES, self.footnotes);
+        options.set(
+            pulldown_cmark::Options::ENABLE_HEADING_ATTRIBUTES,
+            self.heading_attributes,
+        );
+        options.set(
+            pulldown_cmark::Options::ENABLE_SMART_PUNCTUATION,
+            self.smart_punctuation,
+        );
+        options.set(
+            pulldown_cmark::Options::ENABLE_STRIKETHROUGH,
+            self.strikethrough,
+        );
+        options.set(pulldown_cmark::Options::ENABLE_TABLES, self.tables);
+        options.set(pulldown_cmark::Options::ENABLE_TASKLISTS, self.tasklists);
+        options
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ConfigSearch {
+    /// Fold katakana into hiragana when matching
+    pub(crate) kana_folding: bool,
+}
+
 impl Config {
     pub(crate) async fn load_from(path: &std::path::Path) -> anyhow::Result<Self> {
         let config_file_content = tokio::fs::read_to_string(path)
@@ -30,6 +106,15 @@ impl Config {
         Self::load_from(&config_file_path).await
     }
 
+    /// The token required to access the server (as a bearer token or a basic auth password)
+    pub(crate) fn access_token(&self) -> Option<&str> {
+        self.access_token.as_deref()
+    }
+
+    pub(crate) fn bind_address(&self) -> ConfigBindAddress {
+        self.bind_address.clone()
+    }
+
     pub(crate) fn data_dir(&self) -> &std::path::Path {
         &self.data_dir
     }
@@ -42,9 +127,22 @@ impl Config {
         self.data_dir.join("images")
     }
 
+    pub(crate) fn markdown(&self) -> ConfigMarkdown {
+        self.markdown.clone()
+    }
+
     pub(crate) fn port(&self) -> u16 {
         self.port.unwrap_or(3000_u16)
     }
+
+    pub(crate) fn search(&self) -> ConfigSearch {
+        self.search.clone()
+    }
+
+    /// Whether to warn when several pages share a title
+    pub(crate) fn warn_duplicate_titles(&self) -> bool {
+        self.warn_duplicate_titles
+    }
 }
 
 impl std::str::FromStr for Config {
@@ -60,9 +158,14 @@ impl std::str::FromStr for Config {
 
 #[derive(serde::Deserialize)]
 struct ConfigJson {
+    access_token: Option<String>,
+    bind_address: Option<String>,
     data_dir: std::path::PathBuf,
     image_sync: Option<ConfigImageSyncJson>,
+    markdown: Option<ConfigMarkdownJson>,
     port: Option<u16>,
+    search: Option<ConfigSearchJson>,
+    warn_duplicate_titles: Option<bool>,
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize)]
@@ -72,17 +175,46 @@ struct ConfigImageSyncJson {
     object_prefix: String,
 }
 
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct ConfigMarkdownJson {
+    first_line_title: Option<bool>,
+    footnotes: Option<bool>,
+    heading_attributes: Option<bool>,
+    smart_punctuation: Option<bool>,
+    strikethrough: Option<bool>,
+    tables: Option<bool>,
+    tasklists: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct ConfigSearchJson {
+    kana_folding: Option<bool>,
+}
+
 impl TryFrom<ConfigJson> for Config {
     type Error = anyhow::Error;
 
     fn try_from(
         ConfigJson {
+            access_token,
+            bind_address,
             data_dir,
             image_sync,
+            markdown,
             port,
+            search,
+            warn_duplicate_titles,
         }: ConfigJson,
     ) -> Result<Self, Self::Error> {
         Ok(Self {
+            access_token: access_token.filter(|it| !it.is_empty()),
+            bind_address: bind_address
+                .as_deref()
+                .map(<ConfigBindAddress as std::str::FromStr>::from_str)
+                .transpose()?
+                .unwrap_or(ConfigBindAddress::Ip(std::net::IpAddr::V4(
+                    std::net::Ipv4Addr::LOCALHOST,
+                ))),
             data_dir,
             image_sync: image_sync.map(
                 |ConfigImageSyncJson {
@@ -97,7 +229,34 @@ impl TryFrom<ConfigJson> for Config {
                     }
                 },
             ),
+            markdown: markdown
+                .map(
+                    |ConfigMarkdownJson {
+                         first_line_title,
+                         footnotes,
+                         heading_attributes,
+                         smart_punctuation,
+                         strikethrough,
+                         tables,
+                         tasklists,
+                     }| ConfigMarkdown {
+                        first_line_title: first_line_title.unwrap_or(false),
+                        footnotes: footnotes.unwrap_or(false),
+                        heading_attributes: heading_attributes.unwrap_or(false),
+                        smart_punctuation: smart_punctuation.unwrap_or(false),
+                        strikethrough: strikethrough.unwrap_or(false),
+                        tables: tables.unwrap_or(false),
+                        tasklists: tasklists.unwrap_or(false),
+                    },
+                )
+                .unwrap_or_default(),
             port,
+            search: search
+                .map(|ConfigSearchJson { kana_folding }| ConfigSearch {
+                    kana_folding: kana_folding.unwrap_or(false),
+                })
+                .unwrap_or_default(),
+            warn_duplicate_titles: warn_duplicate_titles.unwrap_or(false),
         })
     }
 }
@@ -106,6 +265,60 @@ impl TryFrom<ConfigJson> for Config {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_config_access_token() -> anyhow::Result<()> {
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir"
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(config.access_token(), None);
+
+        let s = r#"
+        {
+            "access_token": "secret",
+            "data_dir": "/path/to/data/dir"
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(config.access_token(), Some("secret"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_bind_address() -> anyhow::Result<()> {
+        let parse = |bind_address: &str| {
+            <Config as std::str::FromStr>::from_str(&format!(
+                r#"{{"bind_address": "{bind_address}", "data_dir": "/path/to/data/dir"}}"#
+            ))
+            .map(|config| config.bind_address())
+        };
+
+        let config =
+            <Config as std::str::FromStr>::from_str(r#"{"data_dir": "/path/to/data/dir"}"#)?;
+        assert_eq!(
+            config.bind_address(),
+            ConfigBindAddress::Ip("127.0.0.1".parse()?)
+        );
+        assert_eq!(parse("0.0.0.0")?, ConfigBindAddress::Ip("0.0.0.0".parse()?));
+        assert_eq!(parse("::1")?, ConfigBindAddress::Ip("::1".parse()?));
+        #[cfg(unix)]
+        assert_eq!(
+            parse("/run/fubako.sock")?,
+            ConfigBindAddress::Unix(std::path::PathBuf::from("/run/fubako.sock"))
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            parse("unix:fubako.sock")?,
+            ConfigBindAddress::Unix(std::path::PathBuf::from("fubako.sock"))
+        );
+        #[cfg(not(unix))]
+        assert!(parse("/run/fubako.sock").is_err());
+        assert!(parse("localhost").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_config_data_dir() -> anyhow::Result<()> {
         let s = r#"
@@ -170,6 +383,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_config_markdown() -> anyhow::Result<()> {
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir"
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(config.markdown(), ConfigMarkdown::default());
+        assert_eq!(
+            config.markdown().options(),
+            pulldown_cmark::Options::ENABLE_WIKILINKS
+        );
+
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir",
+            "markdown": {
+                "first_line_title": true,
+                "footnotes": true,
+                "strikethrough": false,
+                "tables": true
+            }
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(
+            config.markdown(),
+            ConfigMarkdown {
+                first_line_title: true,
+                footnotes: true,
+                tables: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            config.markdown().options(),
+            pulldown_cmark::Options::ENABLE_FOOTNOTES
+                | pulldown_cmark::Options::ENABLE_TABLES
+                | pulldown_cmark::Options::ENABLE_WIKILINKS
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_config_port() -> anyhow::Result<()> {
         let s = r#"
@@ -191,6 +448,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_config_search() -> anyhow::Result<()> {
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir"
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(config.search(), ConfigSearch::default());
+
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir",
+            "search": {
+                "kana_folding": true
+            }
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert_eq!(config.search(), ConfigSearch { kana_folding: true });
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_warn_duplicate_titles() -> anyhow::Result<()> {
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir"
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert!(!config.warn_duplicate_titles());
+
+        let s = r#"
+        {
+            "data_dir": "/path/to/data/dir",
+            "warn_duplicate_titles": true
+        }
+        "#;
+        let config = <Config as std::str::FromStr>::from_str(s)?;
+        assert!(config.warn_duplicate_titles());
+        Ok(())
+    }
+
     #[test]
     fn test_impl_config_load() {
         // TODO: Add test for Config::load
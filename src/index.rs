@@ -0,0 +1,934 @@
+/// The metadata of all pages and the indexes derived from it (backlinks, titles, tags and terms).
+///
+/// The metadata and the sets of pages are shared between clones and copied on write, so a
+/// snapshot does not copy them.
+#[derive(Clone)]
+pub struct Index {
+    pub(crate) backlinks: std::collections::BTreeMap<
+        crate::page_id::PageId,
+        std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+    >,
+    config: crate::config::Config,
+    pub(crate) page_metas: std::collections::BTreeMap<
+        crate::page_id::PageId,
+        std::sync::Arc<crate::page_meta::PageMeta>,
+    >,
+    pub(crate) page_titles: std::collections::BTreeMap<
+        String,
+        std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+    >,
+    /// The pages that have each tag
+    pub(crate) tags: std::collections::BTreeMap<
+        String,
+        std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+    >,
+    /// The full-text index, which is not built for the commands that do not search
+    pub(crate) terms: Option<crate::search::TermIndex>,
+}
+
+impl Index {
+    pub fn new(config: crate::config::Config) -> anyhow::Result<Self> {
+        Self::build(config, true)
+    }
+
+    /// Builds the index without the full-text index. [`Index::search`] matches no pages.
+    pub fn new_without_terms(config: crate::config::Config) -> anyhow::Result<Self> {
+        Self::build(config, false)
+    }
+
+    fn build(config: crate::config::Config, with_terms: bool) -> anyhow::Result<Self> {
+        let page_ids = crate::page_io::PageIo::read_page_ids(&config)?;
+
+        let mut page_titles = std::collections::BTreeMap::<
+            String,
+            std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+        >::new();
+        let mut page_metas = std::collections::BTreeMap::new();
+        let mut tags = std::collections::BTreeMap::<
+            String,
+            std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+        >::new();
+        let mut terms = with_terms.then(|| crate::search::TermIndex::new(config.search()));
+        for page_id in &page_ids {
+            let content = crate::page_io::PageIo::read_page_raw_content(&config, page_id)?;
+            let page_meta = crate::page_meta::PageMeta::from_markdown(&content, &config.markdown());
+            if let Some(terms) = terms.as_mut() {
+                // properties are searched with `prop:key=value` queries, not as text
+                terms.insert(
+                    page_id,
+                    crate::page_meta::PageProperties::split_front_matter(&content).1,
+                );
+            }
+            for title in page_meta.titles() {
+                std::sync::Arc::make_mut(page_titles.entry(title).or_default())
+                    .insert(page_id.clone());
+            }
+            for tag in &page_meta.tags {
+                std::sync::Arc::make_mut(tags.entry(tag.to_owned()).or_default())
+                    .insert(page_id.clone());
+            }
+            page_metas.insert(page_id.clone(), std::sync::Arc::new(page_meta));
+        }
+
+        let mut backlinks = std::collections::BTreeMap::<
+            crate::page_id::PageId,
+            std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+        >::new();
+        for (page_id, page_meta) in &page_metas {
+            for linked_page_id in &resolve_links(&page_titles, page_meta) {
+                std::sync::Arc::make_mut(backlinks.entry(linked_page_id.clone()).or_default())
+                    .insert(page_id.clone());
+            }
+        }
+
+        let index = Self {
+            backlinks,
+            config,
+            page_metas,
+            page_titles,
+            tags,
+            terms,
+        };
+        if index.config.warn_duplicate_titles() {
+            for title in index.duplicate_titles() {
+                index.warn_duplicate_title(title);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Returns the titles shared by several pages.
+    pub fn duplicate_titles(&self) -> Vec<&str> {
+        self.page_titles
+            .iter()
+            .filter(|(_, page_ids)| page_ids.len() > 1)
+            .map(|(title, _)| title.as_str())
+            .collect()
+    }
+
+    pub fn remove(&mut self, page_id: &crate::page_id::PageId) {
+        if let Some(terms) = self.terms.as_mut() {
+            terms.remove(page_id);
+        }
+
+        let old_page_meta = self.page_metas.remove(page_id);
+        match old_page_meta {
+            Some(old_page_meta) => {
+                // remove old links from backlinks
+                for linked_page_id in &resolve_links(&self.page_titles, &old_page_meta) {
+                    if let Some(set) = self.backlinks.get_mut(linked_page_id) {
+                        std::sync::Arc::make_mut(set).remove(page_id);
+                    }
+                }
+
+                // remove from page_titles
+                let old_titles = old_page_meta.titles();
+                self.remove_titles(page_id, &old_titles);
+
+                self.remove_tags(page_id, &old_page_meta);
+
+                // title links to the removed titles no longer resolve to the page
+                if !old_titles.is_empty() {
+                    self.reresolve_backlinks(page_id);
+                }
+            }
+            None => {
+                // do nothing
+            }
+        }
+    }
+
+    /// Returns the pages linked from the page, resolving title links.
+    pub fn links(
+        &self,
+        page_id: &crate::page_id::PageId,
+    ) -> std::collections::BTreeSet<crate::page_id::PageId> {
+        self.page_metas
+            .get(page_id)
+            .map(|page_meta| resolve_links(&self.page_titles, page_meta))
+            .unwrap_or_default()
+    }
+
+    /// Returns the link targets of the page that do not exist: page IDs without a page and
+    /// titles that no page has.
+    pub fn missing_links(
+        &self,
+        page_id: &crate::page_id::PageId,
+    ) -> (
+        std::collections::BTreeSet<crate::page_id::PageId>,
+        std::collections::BTreeSet<String>,
+    ) {
+        match self.page_metas.get(page_id) {
+            None => Default::default(),
+            Some(page_meta) => (
+                page_meta
+                    .links
+                    .iter()
+                    .filter(|it| !self.page_metas.contains_key(it))
+                    .cloned()
+                    .collect(),
+                page_meta
+                    .title_links
+                    .iter()
+                    .filter(|it| !self.page_titles.contains_key(it.as_str()))
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the other pages that link to the pages linked from the page, grouped by the
+    /// shared link target.
+    pub fn two_hop_links(
+        &self,
+        page_id: &crate::page_id::PageId,
+    ) -> std::collections::BTreeMap<
+        crate::page_id::PageId,
+        std::collections::BTreeSet<crate::page_id::PageId>,
+    > {
+        self.links(page_id)
+            .into_iter()
+            .filter_map(|target| {
+                let page_ids = self
+                    .backlinks
+                    .get(&target)?
+                    .iter()
+                    .filter(|it| *it != page_id && **it != target)
+                    .cloned()
+                    .collect::<std::collections::BTreeSet<crate::page_id::PageId>>();
+                (!page_ids.is_empty()).then_some((target, page_ids))
+            })
+            .collect()
+    }
+
+    /// Returns the pages that have the title or the alias.
+    pub fn resolve_title(&self, title: &str) -> Vec<crate::page_id::PageId> {
+        self.page_titles
+            .get(title)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the pages that match the query `q` with their scores, in descending order of score.
+    ///
+    /// See [`crate::search::Query`] for the query syntax.
+    pub fn search(&self, q: &str) -> Vec<(crate::page_id::PageId, f64)> {
+        let Some(terms) = self.terms.as_ref() else {
+            return vec![];
+        };
+        let config = terms.config();
+        let query = crate::search::Query::parse(q, config);
+        let page_ids = terms
+            .candidates(&query)
+            .unwrap_or_else(|| self.page_metas.keys().cloned().collect())
+            .into_iter()
+            .filter(
+                |page_id| match (self.page_metas.get(page_id), terms.text(page_id)) {
+                    (Some(page_meta), Some(text)) => query.matches(
+                        &crate::search::Document {
+                            backlinks: self.backlinks.get(page_id).map(|it| &**it),
+                            page_id,
+                            page_meta,
+                            text,
+                        },
+                        config,
+                    ),
+                    _ => false,
+                },
+            );
+        terms.rank(page_ids, &query.keywords(), |page_id| {
+            self.page_metas
+                .get(page_id)
+                .and_then(|page_meta| page_meta.title.as_deref())
+        })
+    }
+
+    pub fn update(&mut self, page_id: &crate::page_id::PageId) -> anyhow::Result<()> {
+        let content = crate::page_io::PageIo::read_page_raw_content(&self.config, page_id)?;
+        let new_page_meta = std::sync::Arc::new(crate::page_meta::PageMeta::from_markdown(
+            &content,
+            &self.config.markdown(),
+        ));
+        if let Some(terms) = self.terms.as_mut() {
+            terms.insert(
+                page_id,
+                crate::page_meta::PageProperties::split_front_matter(&content).1,
+            );
+        }
+
+        let old_page_meta = self.page_metas.get(page_id).cloned();
+        let old_titles = old_page_meta
+            .as_ref()
+            .map(|page_meta| page_meta.titles())
+            .unwrap_or_default();
+        match old_page_meta {
+            Some(old_page_meta) => {
+                // remove old links from backlinks
+                for linked_page_id in &resolve_links(&self.page_titles, &old_page_meta) {
+                    if let Some(set) = self.backlinks.get_mut(linked_page_id) {
+                        std::sync::Arc::make_mut(set).remove(page_id);
+                    }
+                }
+
+                self.remove_tags(page_id, &old_page_meta);
+
+                // remove old titles from page_titles
+                self.remove_titles(page_id, &old_titles);
+            }
+            None => {
+                // do nothing
+            }
+        }
+
+        self.page_metas
+            .insert(page_id.clone(), new_page_meta.clone());
+        let new_titles = new_page_meta.titles();
+        for new_title in &new_titles {
+            let page_ids =
+                std::sync::Arc::make_mut(self.page_titles.entry(new_title.to_owned()).or_default());
+            page_ids.insert(page_id.clone());
+            if page_ids.len() > 1
+                && !old_titles.contains(new_title)
+                && self.config.warn_duplicate_titles()
+            {
+                self.warn_duplicate_title(new_title);
+            }
+        }
+
+        for tag in &new_page_meta.tags {
+            std::sync::Arc::make_mut(self.tags.entry(tag.to_owned()).or_default())
+                .insert(page_id.clone());
+        }
+
+        for linked_page_id in &resolve_links(&self.page_titles, &new_page_meta) {
+            std::sync::Arc::make_mut(self.backlinks.entry(linked_page_id.clone()).or_default())
+                .insert(page_id.clone());
+        }
+
+        // title links to the old or new titles now resolve differently
+        if old_titles != new_titles {
+            self.reresolve_backlinks(page_id);
+        }
+
+        Ok(())
+    }
+
+    fn remove_titles(
+        &mut self,
+        page_id: &crate::page_id::PageId,
+        titles: &std::collections::BTreeSet<String>,
+    ) {
+        for title in titles {
+            if let Some(set) = self.page_titles.get_mut(title) {
+                std::sync::Arc::make_mut(set).remove(page_id);
+                if set.is_empty() {
+                    self.page_titles.remove(title);
+                }
+            }
+        }
+    }
+
+    fn remove_tags(
+        &mut self,
+        page_id: &crate::page_id::PageId,
+        page_meta: &crate::page_meta::PageMeta,
+    ) {
+        for tag in &page_meta.tags {
+            if let Some(set) = self.tags.get_mut(tag) {
+                std::sync::Arc::make_mut(set).remove(page_id);
+                if set.is_empty() {
+                    self.tags.remove(tag);
+                }
+            }
+        }
+    }
+
+    fn warn_duplicate_title(&self, title: &str) {
+        let page_ids = self
+            .resolve_title(title)
+            .iter()
+            .map(|page_id| page_id.to_string())
+            .collect::<Vec<String>>();
+        eprintln!(
+            "warning: duplicate title {:?}: {}",
+            title,
+            page_ids.join(", ")
+        );
+    }
+
+    /// Rebuilds the backlinks of `page_id` from the links of all pages.
+    fn reresolve_backlinks(&mut self, page_id: &crate::page_id::PageId) {
+        let set = self
+            .page_metas
+            .iter()
+            .filter(|(_, page_meta)| resolve_links(&self.page_titles, page_meta).contains(page_id))
+            .map(|(linking_page_id, _)| linking_page_id.clone())
+            .collect::<std::collections::BTreeSet<crate::page_id::PageId>>();
+        if set.is_empty() {
+            self.backlinks.remove(page_id);
+        } else {
+            self.backlinks
+                .insert(page_id.clone(), std::sync::Arc::new(set));
+        }
+    }
+}
+
+/// Returns the pages linked from `page_meta`, resolving title links by `page_titles`.
+fn resolve_links(
+    page_titles: &std::collections::BTreeMap<
+        String,
+        std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+    >,
+    page_meta: &crate::page_meta::PageMeta,
+) -> std::collections::BTreeSet<crate::page_id::PageId> {
+    page_meta
+        .links
+        .iter()
+        .cloned()
+        .chain(
+            page_meta
+                .title_links
+                .iter()
+                .filter_map(|title| page_titles.get(title))
+                .flat_map(|page_ids| page_ids.iter())
+                .cloned(),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::create_page;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_new() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(
+            &data_dir,
+            "20251224T000000Z",
+            r#"
+# Test Page 1
+
+This is a test page.
+"#,
+        )?;
+        let page2_id = create_page(
+            &data_dir,
+            "20251224T000001Z",
+            r#"---
+# Test Page 2
+
+Link to [[20251224T000000Z]].
+"#,
+        )?;
+
+        let index = Index::new(config)?;
+
+        assert_eq!(
+            index.page_metas,
+            [
+                (
+                    page1_id.clone(),
+                    std::sync::Arc::new(crate::page_meta::PageMeta {
+                        title: Some("Test Page 1".to_owned()),
+                        links: std::collections::BTreeSet::new(),
+                        properties: Default::default(),
+                        tags: Default::default(),
+                        title_links: std::collections::BTreeSet::new(),
+                    }),
+                ),
+                (
+                    page2_id.clone(),
+                    std::sync::Arc::new(crate::page_meta::PageMeta {
+                        title: Some("Test Page 2".to_owned()),
+                        links: [page1_id.clone()]
+                            .into_iter()
+                            .collect::<std::collections::BTreeSet<_>>(),
+                        properties: Default::default(),
+                        tags: Default::default(),
+                        title_links: std::collections::BTreeSet::new(),
+                    })
+                ),
+            ]
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>()
+        );
+
+        assert_eq!(
+            index.page_titles,
+            [
+                (
+                    "Test Page 1".to_owned(),
+                    std::sync::Arc::new(
+                        [page1_id.clone()]
+                            .into_iter()
+                            .collect::<std::collections::BTreeSet<_>>(),
+                    ),
+                ),
+                (
+                    "Test Page 2".to_owned(),
+                    std::sync::Arc::new(
+                        [page2_id.clone()]
+                            .into_iter()
+                            .collect::<std::collections::BTreeSet<_>>(),
+                    ),
+                )
+            ]
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>()
+        );
+
+        assert_eq!(
+            index.backlinks,
+            [(
+                page1_id.clone(),
+                std::sync::Arc::new(
+                    [page2_id.clone()]
+                        .into_iter()
+                        .collect::<std::collections::BTreeSet<_>>(),
+                ),
+            )]
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(
+            &data_dir,
+            "20251224T000000Z",
+            r#"
+# Test Page 1
+
+This is a test page.
+"#,
+        )?;
+        let page2_id = create_page(
+            &data_dir,
+            "20251224T000001Z",
+            r#"
+# Test Page 2
+
+Link to [[20251224T000000Z]].
+"#,
+        )?;
+        let page3_id = create_page(
+            &data_dir,
+            "20251224T000002Z",
+            r#"---
+# Test Page 3
+
+Link to [[20251224T000000Z]].
+"#,
+        )?;
+
+        let mut index = Index::new(config)?;
+
+        // verify initial state
+        assert_eq!(index.page_metas.len(), 3);
+        assert!(index.page_titles.contains_key("Test Page 1"));
+        assert!(index.page_titles.contains_key("Test Page 2"));
+        assert!(index.page_titles.contains_key("Test Page 3"));
+        assert!(
+            // page1
+            // page2 -> page1
+            // page3 -> page1
+            index
+                .backlinks
+                .get(&page1_id)
+                .map(|it| it.len() == 2 && it.contains(&page2_id) && it.contains(&page3_id))
+                .unwrap_or(false)
+        );
+
+        index.remove(&page2_id);
+
+        assert_eq!(index.page_metas.len(), 2);
+        assert!(!index.page_metas.contains_key(&page2_id));
+        assert!(!index.page_titles.contains_key("Test Page 2"));
+        assert!(
+            index
+                .backlinks
+                .get(&page1_id)
+                .map(|it| it.len() == 1 && it.contains(&page3_id))
+                .unwrap_or(false)
+        );
+
+        index.remove(&page1_id);
+
+        assert_eq!(index.page_metas.len(), 1);
+        assert!(!index.page_metas.contains_key(&page1_id));
+        assert!(!index.page_titles.contains_key("Test Page 1"));
+
+        // removing non-existent page should not cause any issues
+        let non_existent_id = crate::page_id::PageId::from_str("20251225T000000Z")?;
+        index.remove(&non_existent_id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(&data_dir, "20251224T000000Z", "# Rust\n\nOwnership.")?;
+        let page2_id = create_page(
+            &data_dir,
+            "20251224T000001Z",
+            "# Go\n\nGoroutine. [20251224T000000Z]",
+        )?;
+        let page3_id = create_page(
+            &data_dir,
+            "20251224T000002Z",
+            "---\nstatus: ownership\n---\n# C\n",
+        )?;
+
+        // the metadata is indexed without the terms
+        let index = Index::new_without_terms(config.clone())?;
+        assert_eq!(index.page_metas.len(), 3);
+        assert!(index.terms.is_none());
+        assert!(search(&index, "ownership").is_empty());
+
+        let mut index = Index::new(config)?;
+        assert_eq!(
+            search(&index, "ownership"),
+            [page1_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        // the front matter is not indexed as text
+        assert_eq!(
+            search(&index, "prop:status=ownership"),
+            [page3_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        assert_eq!(
+            search(&index, "links:20251224T000000Z"),
+            [page2_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+        assert_eq!(
+            search(&index, "linkedfrom:20251224T000001Z -title:go"),
+            [page1_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+        assert_eq!(
+            search(&index, "ownership OR goroutine"),
+            [page1_id.clone(), page2_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        std::fs::write(
+            data_dir.join(page2_id.to_string()).with_extension("md"),
+            "# Go\n\nOwnership?",
+        )?;
+        index.update(&page2_id)?;
+        assert_eq!(
+            search(&index, "ownership"),
+            [page1_id.clone(), page2_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        index.remove(&page1_id);
+        assert_eq!(
+            search(&index, "ownership"),
+            [page2_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_title_links() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(&data_dir, "20251224T000000Z", "# Page 1\n")?;
+        let page2_id = create_page(&data_dir, "20251224T000001Z", "# Page 2\n\n[[Page 1]]")?;
+        let page3_id = create_page(&data_dir, "20251224T000002Z", "# Page 3\n")?;
+
+        let mut index = Index::new(config)?;
+        assert_eq!(index.resolve_title("Page 1"), vec![page1_id.clone()]);
+        assert!(index.resolve_title("Page 4").is_empty());
+        assert_eq!(
+            index.backlinks.get(&page1_id).map(|it| &**it),
+            Some(
+                &[page2_id.clone()]
+                    .into_iter()
+                    .collect::<std::collections::BTreeSet<_>>()
+            )
+        );
+
+        std::fs::write(
+            data_dir.join(page3_id.to_string()).with_extension("md"),
+            "# Page 3\n\n[[Page 1|label]]",
+        )?;
+        index.update(&page3_id)?;
+        assert_eq!(
+            index.backlinks.get(&page1_id).map(|it| &**it),
+            Some(
+                &[page2_id.clone(), page3_id.clone()]
+                    .into_iter()
+                    .collect::<std::collections::BTreeSet<_>>()
+            )
+        );
+
+        assert_eq!(
+            index.links(&page3_id),
+            [page1_id.clone()]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<_>>()
+        );
+
+        index.remove(&page2_id);
+        assert_eq!(
+            index.backlinks.get(&page1_id).map(|it| &**it),
+            Some(
+                &[page3_id.clone()]
+                    .into_iter()
+                    .collect::<std::collections::BTreeSet<_>>()
+            )
+        );
+
+        // a page linking to a missing title is resolved when the title appears
+        let page4_id = create_page(
+            &data_dir,
+            "20251224T000003Z",
+            "# Page 4\n\n[new](/titles/Page%205)",
+        )?;
+        index.update(&page4_id)?;
+        assert_eq!(index.backlinks.get(&page3_id).map(|it| &**it), None);
+        std::fs::write(
+            data_dir.join(page3_id.to_string()).with_extension("md"),
+            "# Page 5\n\n[[Page 1|label]]",
+        )?;
+        index.update(&page3_id)?;
+        assert_eq!(
+            index.backlinks.get(&page3_id).map(|it| &**it),
+            Some(
+                &[page4_id.clone()]
+                    .into_iter()
+                    .collect::<std::collections::BTreeSet<_>>()
+            )
+        );
+
+        // and unresolved when the title changes or the page is removed
+        std::fs::write(
+            data_dir.join(page3_id.to_string()).with_extension("md"),
+            "# Page 3\n\n[[Page 1|label]]",
+        )?;
+        index.update(&page3_id)?;
+        assert_eq!(index.backlinks.get(&page3_id).map(|it| &**it), None);
+        std::fs::write(
+            data_dir.join(page1_id.to_string()).with_extension("md"),
+            "# Page 5\n",
+        )?;
+        index.update(&page1_id)?;
+        assert_eq!(
+            index.backlinks.get(&page1_id).map(|it| &**it),
+            Some(
+                &[page4_id.clone()]
+                    .into_iter()
+                    .collect::<std::collections::BTreeSet<_>>()
+            )
+        );
+        index.remove(&page1_id);
+        assert_eq!(index.backlinks.get(&page1_id).map(|it| &**it), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_titles() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir_with(serde_json::json!({
+            "warn_duplicate_titles": true
+        }))?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        create_page(&data_dir, "20251224T000000Z", "# Same\n")?;
+        create_page(&data_dir, "20251224T000001Z", "# Same\n")?;
+        let page3_id = create_page(&data_dir, "20251224T000002Z", "# Other\n")?;
+
+        let mut index = Index::new(config)?;
+        assert_eq!(index.duplicate_titles(), vec!["Same"]);
+
+        std::fs::write(
+            data_dir.join(page3_id.to_string()).with_extension("md"),
+            "# Same\n",
+        )?;
+        index.update(&page3_id)?;
+        assert_eq!(index.duplicate_titles(), vec!["Same"]);
+        assert_eq!(index.resolve_title("Same").len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_aliases() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(
+            &data_dir,
+            "20251224T000000Z",
+            "---\naliases: [HTTP, ハイパーテキスト転送プロトコル]\n---\n# Hypertext Transfer Protocol\n",
+        )?;
+        let page2_id = create_page(&data_dir, "20251224T000001Z", "# Page 2\n\n[[HTTP]]\n")?;
+
+        let mut index = Index::new(config)?;
+        assert_eq!(index.resolve_title("HTTP"), vec![page1_id.clone()]);
+        assert_eq!(
+            index.resolve_title("ハイパーテキスト転送プロトコル"),
+            vec![page1_id.clone()]
+        );
+        assert_eq!(
+            index.resolve_title("Hypertext Transfer Protocol"),
+            vec![page1_id.clone()]
+        );
+        assert_eq!(
+            index.backlinks.get(&page1_id).map(|it| &**it),
+            Some(&[page2_id.clone()].into_iter().collect())
+        );
+
+        // removing the alias
+        std::fs::write(
+            data_dir.join(page1_id.to_string()).with_extension("md"),
+            "# Hypertext Transfer Protocol\n",
+        )?;
+        index.update(&page1_id)?;
+        assert!(index.resolve_title("HTTP").is_empty());
+        assert_eq!(index.backlinks.get(&page1_id).map(|it| &**it), None);
+
+        index.remove(&page1_id);
+        assert!(index.page_titles.keys().all(|title| title == "Page 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_links_and_two_hop_links() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(
+            &data_dir,
+            "20251224T000000Z",
+            "# Page 1\n\n[[Page 2]] [[Page 3]] [[Missing]] [20251231T000000Z]\n",
+        )?;
+        let page2_id = create_page(&data_dir, "20251224T000001Z", "# Page 2\n")?;
+        let page3_id = create_page(&data_dir, "20251224T000002Z", "# Page 3\n\n[[Page 2]]\n")?;
+        let page4_id = create_page(
+            &data_dir,
+            "20251224T000003Z",
+            "# Page 4\n\n[[Page 2]] [[Page 3]] [[Missing]]\n",
+        )?;
+
+        let index = Index::new(config)?;
+
+        assert_eq!(
+            index.missing_links(&page1_id),
+            (
+                [crate::page_id::PageId::from_str("20251231T000000Z")?]
+                    .into_iter()
+                    .collect(),
+                ["Missing".to_owned()].into_iter().collect()
+            )
+        );
+        assert_eq!(index.missing_links(&page2_id), Default::default());
+
+        assert_eq!(
+            index.two_hop_links(&page1_id),
+            [
+                // page 3 is also linked directly
+                (
+                    page2_id.clone(),
+                    [page3_id.clone(), page4_id.clone()].into_iter().collect()
+                ),
+                (page3_id.clone(), [page4_id.clone()].into_iter().collect()),
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            index.two_hop_links(&page3_id),
+            [(
+                page2_id.clone(),
+                [page1_id.clone(), page4_id.clone()].into_iter().collect()
+            )]
+            .into_iter()
+            .collect()
+        );
+        assert!(index.two_hop_links(&page2_id).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tags() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir().to_path_buf();
+
+        let page1_id = create_page(&data_dir, "20251224T000000Z", "# Page 1\n\n#rust #cli\n")?;
+        let page2_id = create_page(
+            &data_dir,
+            "20251224T000001Z",
+            "---\ntags: [rust]\n---\n# Page 2\n",
+        )?;
+
+        let tags = |index: &Index| {
+            index
+                .tags
+                .iter()
+                .map(|(tag, page_ids)| (tag.clone(), page_ids.len()))
+                .collect::<Vec<(String, usize)>>()
+        };
+        let mut index = Index::new(config)?;
+        assert_eq!(
+            tags(&index),
+            vec![("cli".to_owned(), 1), ("rust".to_owned(), 2)]
+        );
+
+        std::fs::write(
+            data_dir.join(page1_id.to_string()).with_extension("md"),
+            "# Page 1\n\n#cli\n",
+        )?;
+        index.update(&page1_id)?;
+        assert_eq!(
+            tags(&index),
+            vec![("cli".to_owned(), 1), ("rust".to_owned(), 1)]
+        );
+
+        index.remove(&page2_id);
+        assert_eq!(tags(&index), vec![("cli".to_owned(), 1)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update() -> anyhow::Result<()> {
+        // TODO: Add test for Index::remove
+        Ok(())
+    }
+
+    fn search(index: &Index, q: &str) -> std::collections::BTreeSet<crate::page_id::PageId> {
+        index
+            .search(q)
+            .into_iter()
+            .map(|(page_id, _)| page_id)
+            .collect::<std::collections::BTreeSet<_>>()
+    }
+}
@@ -1,8 +1,12 @@
 mod config;
+mod index;
 mod page_id;
 mod page_io;
 mod page_meta;
+mod search;
 mod subcommand;
+#[cfg(test)]
+mod test_util;
 mod util;
 
 #[derive(clap::Parser)]
@@ -11,6 +11,13 @@ impl PageId {
     pub fn root() -> Self {
         Self("README".to_owned())
     }
+
+    /// Returns the creation time encoded in the ID (`None` for the root page).
+    pub fn created_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::NaiveDateTime::parse_from_str(&self.0, "%Y%m%dT%H%M%SZ")
+            .ok()
+            .map(|it| it.and_utc())
+    }
 }
 
 impl std::fmt::Display for PageId {
@@ -78,6 +85,17 @@ mod tests {
         assert_eq!(page_id.0, "README");
     }
 
+    #[test]
+    fn test_impl_page_id_created_at() -> anyhow::Result<()> {
+        let page_id = <PageId as std::str::FromStr>::from_str("20240620T123456Z")?;
+        assert_eq!(
+            page_id.created_at().map(|it| it.to_rfc3339()),
+            Some("2024-06-20T12:34:56+00:00".to_owned())
+        );
+        assert_eq!(PageId::root().created_at(), None);
+        Ok(())
+    }
+
     #[test]
     fn test_impl_display_for_page_id() -> anyhow::Result<()> {
         let s = "20240620T123456Z";
@@ -1,15 +1,27 @@
 use anyhow::Context as _;
 
+static SYNTAX_SET: std::sync::LazyLock<syntect::parsing::SyntaxSet> =
+    std::sync::LazyLock::new(syntect::parsing::SyntaxSet::load_defaults_newlines);
+static THEME_SET: std::sync::LazyLock<syntect::highlighting::ThemeSet> =
+    std::sync::LazyLock::new(syntect::highlighting::ThemeSet::load_defaults);
+
 pub(crate) struct PageIo;
 
 impl PageIo {
+    /// Creates the page with `content`. Fails if the page already exists.
     pub(crate) fn create_page(
         config: &crate::config::Config,
         page_id: &crate::page_id::PageId,
+        content: &str,
     ) -> anyhow::Result<std::path::PathBuf> {
         let path_buf = Self::page_path(config, page_id);
         std::fs::create_dir_all(path_buf.parent().context("invalid path")?)?;
-        std::fs::write(&path_buf, "")?;
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path_buf)
+            .context("page already exists")?;
+        std::io::Write::write_all(&mut file, content.as_bytes())?;
         Ok(path_buf)
     }
 
@@ -22,6 +34,18 @@ impl PageIo {
         Ok(page_id)
     }
 
+    pub(crate) fn page_modified(
+        config: &crate::config::Config,
+        page_id: &crate::page_id::PageId,
+    ) -> anyhow::Result<std::time::SystemTime> {
+        let path = Self::page_path(config, page_id);
+        let modified = std::fs::metadata(path)
+            .context("not found")?
+            .modified()
+            .context("modified")?;
+        Ok(modified)
+    }
+
     pub(crate) fn page_path(
         config: &crate::config::Config,
         page_id: &crate::page_id::PageId,
@@ -40,7 +64,8 @@ impl PageIo {
         for dir_entry in read_dir {
             let dir_entry = dir_entry.context("dir_entry")?;
             let path_buf = dir_entry.path();
-            if !path_buf.is_file() {
+            // skip directories and non-page files such as editor swap files
+            if !path_buf.is_file() || path_buf.extension().is_none_or(|it| it != "md") {
                 continue;
             }
 
@@ -53,21 +78,17 @@ impl PageIo {
         Ok(page_ids)
     }
 
-    pub(crate) fn read_page_meta(
-        config: &crate::config::Config,
-        page_id: &crate::page_id::PageId,
-    ) -> anyhow::Result<crate::page_meta::PageMeta> {
-        let content = Self::read_page_raw_content(config, page_id)?;
-        let page_meta = crate::page_meta::PageMeta::from_markdown(&content);
-        Ok(page_meta)
-    }
-
-    pub(crate) fn read_page_content(
+    /// Renders the page. `resolve_title` returns the pages that have the title.
+    pub(crate) fn read_page_content<F>(
         config: &crate::config::Config,
         page_id: &crate::page_id::PageId,
-    ) -> anyhow::Result<String> {
+        resolve_title: F,
+    ) -> anyhow::Result<String>
+    where
+        F: Fn(&str) -> Vec<crate::page_id::PageId>,
+    {
         let content = Self::read_page_raw_content(config, page_id)?;
-        convert_to_html(content)
+        convert_to_html(content, &config.markdown(), resolve_title)
     }
 
     pub(crate) fn read_page_raw_content(
@@ -78,16 +99,73 @@ impl PageIo {
         let md = std::fs::read_to_string(path).context("not found")?;
         Ok(md)
     }
+
+    /// Writes `content` to the page and returns the new modification time.
+    ///
+    /// Fails with [`PageConflict`] if the file has been modified since `expected_modified`.
+    /// The content is written to a temporary file first and renamed into place, so the page is
+    /// never left half-written and a change made while writing is not overwritten.
+    pub(crate) fn write_page(
+        config: &crate::config::Config,
+        page_id: &crate::page_id::PageId,
+        content: &str,
+        expected_modified: std::time::SystemTime,
+    ) -> anyhow::Result<std::time::SystemTime> {
+        if Self::page_modified(config, page_id)? != expected_modified {
+            return Err(anyhow::Error::new(PageConflict));
+        }
+        let path = Self::page_path(config, page_id);
+        // not a `.md` file, so it is ignored by the watcher and `read_page_ids`
+        let temp_path = config
+            .data_dir()
+            .join(format!(".{page_id}.md.{}.tmp", std::process::id()));
+        let result = (|| {
+            std::fs::write(&temp_path, content).context("write")?;
+            std::fs::set_permissions(&temp_path, std::fs::metadata(&path)?.permissions())?;
+            if Self::page_modified(config, page_id)? != expected_modified {
+                return Err(anyhow::Error::new(PageConflict));
+            }
+            std::fs::rename(&temp_path, &path).context("rename")?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        result?;
+        Self::page_modified(config, page_id)
+    }
+}
+
+/// The page has been modified by someone else.
+#[derive(Debug)]
+pub(crate) struct PageConflict;
+
+impl std::fmt::Display for PageConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "page has been modified since it was read")
+    }
 }
 
-fn convert_to_html(md: String) -> anyhow::Result<String> {
-    let syntax_set = syntect::parsing::SyntaxSet::load_defaults_newlines();
-    let theme_set = syntect::highlighting::ThemeSet::load_defaults();
+impl std::error::Error for PageConflict {}
+
+fn convert_to_html<F>(
+    md: String,
+    config: &crate::config::ConfigMarkdown,
+    resolve_title: F,
+) -> anyhow::Result<String>
+where
+    F: Fn(&str) -> Vec<crate::page_id::PageId>,
+{
+    let syntax_set = &*SYNTAX_SET;
+    let theme_set = &*THEME_SET;
+
+    let (_, md) = crate::page_meta::PageProperties::split_front_matter(&md);
 
     let mut start_fenced_code_block_with_info_string = None;
+    let mut in_wiki_link = false;
     let parser = pulldown_cmark::Parser::new_with_broken_link_callback(
-        &md,
-        pulldown_cmark::Options::empty(),
+        md,
+        config.options(),
         Some(|broken_link: pulldown_cmark::BrokenLink<'_>| {
             match <crate::page_id::PageId as std::str::FromStr>::from_str(&broken_link.reference) {
                 Err(_) => None,
@@ -99,7 +177,71 @@ fn convert_to_html(md: String) -> anyhow::Result<String> {
         }),
     );
     let parser = pulldown_cmark::TextMergeStream::new(parser);
+    let mut in_code_block = false;
+    let mut in_link = false;
+    let mut prev_char = None;
+    let parser = parser.flat_map(|event| {
+        let char_before = prev_char;
+        prev_char = crate::page_meta::last_inline_char(prev_char, &event);
+        match event {
+            pulldown_cmark::Event::Start(pulldown_cmark::Tag::CodeBlock(_)) => {
+                in_code_block = true;
+                vec![event]
+            }
+            pulldown_cmark::Event::End(pulldown_cmark::TagEnd::CodeBlock) => {
+                in_code_block = false;
+                vec![event]
+            }
+            pulldown_cmark::Event::Start(
+                pulldown_cmark::Tag::Image { .. } | pulldown_cmark::Tag::Link { .. },
+            ) => {
+                in_link = true;
+                vec![event]
+            }
+            pulldown_cmark::Event::End(
+                pulldown_cmark::TagEnd::Image | pulldown_cmark::TagEnd::Link,
+            ) => {
+                in_link = false;
+                vec![event]
+            }
+            pulldown_cmark::Event::Text(text) if !in_code_block && !in_link => {
+                link_hashtags(text, char_before)
+            }
+            _ => vec![event],
+        }
+    });
     let parser = parser.filter_map(|event| match event {
+        pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
+            link_type: pulldown_cmark::LinkType::WikiLink { .. },
+            dest_url,
+            ..
+        }) => {
+            in_wiki_link = true;
+            let target = dest_url.trim();
+            let html = match <crate::page_id::PageId as std::str::FromStr>::from_str(target) {
+                Ok(page_id) => format!(r#"<a href="/pages/{page_id}">"#),
+                Err(_) => match resolve_title(target).as_slice() {
+                    [] => format!(
+                        r#"<a class="missing_page" href="/titles/{}">"#,
+                        crate::util::percent_encode(target)
+                    ),
+                    [page_id] => format!(r#"<a href="/pages/{page_id}">"#),
+                    [_, _, ..] => format!(
+                        r#"<a href="/titles/{}">"#,
+                        crate::util::percent_encode(target)
+                    ),
+                },
+            };
+            Some(pulldown_cmark::Event::Html(pulldown_cmark::CowStr::Boxed(
+                html.into_boxed_str(),
+            )))
+        }
+        pulldown_cmark::Event::End(pulldown_cmark::TagEnd::Link) if in_wiki_link => {
+            in_wiki_link = false;
+            Some(pulldown_cmark::Event::Html(
+                pulldown_cmark::CowStr::Borrowed("</a>"),
+            ))
+        }
         pulldown_cmark::Event::Start(pulldown_cmark::Tag::CodeBlock(
             pulldown_cmark::CodeBlockKind::Fenced(info_string),
         )) => {
@@ -111,7 +253,7 @@ fn convert_to_html(md: String) -> anyhow::Result<String> {
             let result = if let Some(info_string) = &start_fenced_code_block_with_info_string {
                 let html = syntect::html::highlighted_html_for_string(
                     cow_str.as_ref(),
-                    &syntax_set,
+                    syntax_set,
                     syntax_set
                         .find_syntax_by_token(&info_string)
                         .unwrap_or_else(|| syntax_set.find_syntax_plain_text()),
@@ -135,3 +277,174 @@ fn convert_to_html(md: String) -> anyhow::Result<String> {
     pulldown_cmark::html::push_html(&mut html, parser);
     Ok(html)
 }
+
+/// Splits `text` into events that render each `#tag` as a link to `/tags/{tag}`, where `prev` is
+/// the character before `text` (see [`crate::page_meta::hashtags`]).
+fn link_hashtags(
+    text: pulldown_cmark::CowStr<'_>,
+    prev: Option<char>,
+) -> Vec<pulldown_cmark::Event<'_>> {
+    let ranges = crate::page_meta::hashtags(&text, prev);
+    if ranges.is_empty() {
+        return vec![pulldown_cmark::Event::Text(text)];
+    }
+    let text_event = |s: &str| {
+        pulldown_cmark::Event::Text(pulldown_cmark::CowStr::Boxed(s.to_owned().into_boxed_str()))
+    };
+    let mut events = vec![];
+    let mut pos = 0;
+    for range in ranges {
+        if pos < range.start {
+            events.push(text_event(&text[pos..range.start]));
+        }
+        events.push(pulldown_cmark::Event::Html(pulldown_cmark::CowStr::Boxed(
+            format!(
+                r#"<a class="tag" href="/tags/{}">"#,
+                crate::util::percent_encode(&text[range.start + 1..range.end])
+            )
+            .into_boxed_str(),
+        )));
+        events.push(text_event(&text[range.clone()]));
+        events.push(pulldown_cmark::Event::Html(
+            pulldown_cmark::CowStr::Borrowed("</a>"),
+        ));
+        pos = range.end;
+    }
+    if pos < text.len() {
+        events.push(text_event(&text[pos..]));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_page_ids() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let data_dir = temp_dir.path().join("data");
+        std::fs::create_dir_all(data_dir.join("images"))?;
+        std::fs::write(data_dir.join("20251224T000000Z.md"), "")?;
+        std::fs::write(data_dir.join("README.md"), "")?;
+        std::fs::write(data_dir.join(".20251224T000000Z.md.swp"), "")?;
+        std::fs::write(data_dir.join("20251224T000000Z.md~"), "")?;
+        let config = <crate::config::Config as std::str::FromStr>::from_str(&format!(
+            r#"{{"data_dir": "{}"}}"#,
+            data_dir.display()
+        ))?;
+        assert_eq!(
+            PageIo::read_page_ids(&config)?,
+            [
+                <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000000Z")?,
+                crate::page_id::PageId::root(),
+            ]
+            .into_iter()
+            .collect::<std::collections::BTreeSet<crate::page_id::PageId>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_page() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let data_dir = temp_dir.path().join("data");
+        std::fs::create_dir_all(&data_dir)?;
+        let config = <crate::config::Config as std::str::FromStr>::from_str(&format!(
+            r#"{{"data_dir": "{}"}}"#,
+            data_dir.display()
+        ))?;
+        let page_id = crate::page_id::PageId::new();
+        PageIo::create_page(&config, &page_id, "")?;
+        // the handlers respond with `409 Conflict` to this error
+        let error = PageIo::create_page(&config, &page_id, "overwritten").unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<std::io::Error>().map(|e| e.kind()),
+            Some(std::io::ErrorKind::AlreadyExists)
+        );
+
+        let modified = PageIo::page_modified(&config, &page_id)?;
+        let new_modified = PageIo::write_page(&config, &page_id, "# Title\n", modified)?;
+        assert_eq!(
+            PageIo::read_page_raw_content(&config, &page_id)?,
+            "# Title\n"
+        );
+
+        // stale modification time
+        let stale = new_modified - std::time::Duration::from_secs(1);
+        let err = PageIo::write_page(&config, &page_id, "overwritten", stale).unwrap_err();
+        assert!(err.downcast_ref::<PageConflict>().is_some());
+        assert_eq!(
+            PageIo::read_page_raw_content(&config, &page_id)?,
+            "# Title\n"
+        );
+        // no temporary file is left
+        assert_eq!(std::fs::read_dir(&data_dir)?.count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_html_wiki_link() -> anyhow::Result<()> {
+        let page1_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000000Z")?;
+        let page2_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000001Z")?;
+        let resolve_title = |title: &str| match title {
+            "Unique" => vec![page1_id.clone()],
+            "Duplicate" => vec![page1_id.clone(), page2_id.clone()],
+            _ => vec![],
+        };
+        let convert = |md: &str| convert_to_html(md.to_owned(), &Default::default(), resolve_title);
+
+        assert_eq!(
+            convert("[[Unique]]")?,
+            "<p><a href=\"/pages/20251224T000000Z\">Unique</a></p>\n"
+        );
+        assert_eq!(
+            convert("[[Unique|label]]")?,
+            "<p><a href=\"/pages/20251224T000000Z\">label</a></p>\n"
+        );
+        assert_eq!(
+            convert("[[20251224T000001Z]]")?,
+            "<p><a href=\"/pages/20251224T000001Z\">20251224T000001Z</a></p>\n"
+        );
+        assert_eq!(
+            convert("[[Duplicate]]")?,
+            "<p><a href=\"/titles/Duplicate\">Duplicate</a></p>\n"
+        );
+        assert_eq!(
+            convert("[[Missing Page]] [link](/README)")?,
+            "<p><a class=\"missing_page\" href=\"/titles/Missing%20Page\">Missing Page</a> <a href=\"/README\">link</a></p>\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_html_tag() -> anyhow::Result<()> {
+        let convert = |md: &str| convert_to_html(md.to_owned(), &Default::default(), |_| vec![]);
+        assert_eq!(
+            convert("#rust and #日本語.")?,
+            "<p><a class=\"tag\" href=\"/tags/rust\">#rust</a> and <a class=\"tag\" href=\"/tags/%E6%97%A5%E6%9C%AC%E8%AA%9E\">#日本語</a>.</p>\n"
+        );
+        assert_eq!(
+            convert("`#code` [#link](/README) ![#alt](/images/a.png)")?,
+            "<p><code>#code</code> <a href=\"/README\">#link</a> <img src=\"/images/a.png\" alt=\"#alt\" /></p>\n"
+        );
+        assert_eq!(
+            convert("**b**#strong `x`#code *e* #ok")?,
+            "<p><strong>b</strong>#strong <code>x</code>#code <em>e</em> <a class=\"tag\" href=\"/tags/ok\">#ok</a></p>\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_html_front_matter() -> anyhow::Result<()> {
+        assert_eq!(
+            convert_to_html(
+                "---\ntags: [rust]\n---\n# Title\n".to_owned(),
+                &Default::default(),
+                |_| vec![]
+            )?,
+            "<h1>Title</h1>\n"
+        );
+        Ok(())
+    }
+}
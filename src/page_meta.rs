@@ -1,21 +1,36 @@
+mod properties;
+
 use crate::page_id::PageId;
 
+pub use self::properties::PageProperties;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct PageMeta {
     pub links: std::collections::BTreeSet<PageId>,
+    /// The properties declared in the front matter
+    pub properties: PageProperties,
+    /// The inline `#tag`s and the tags declared in the front matter (without `#`)
+    pub tags: std::collections::BTreeSet<String>,
     pub title: Option<String>,
+    /// The titles linked by `[[Title]]` or `/titles/{title}`, resolved to pages by the title index
+    pub title_links: std::collections::BTreeSet<String>,
 }
 
 impl PageMeta {
-    pub fn from_markdown(md: &str) -> Self {
+    /// Extracts the metadata with the same Markdown extensions used for rendering.
+    pub fn from_markdown(md: &str, config: &crate::config::ConfigMarkdown) -> Self {
+        let (properties, md) = PageProperties::split_front_matter(md);
         let mut page_meta = PageMeta {
             links: Default::default(),
+            properties,
+            tags: Default::default(),
             title: Default::default(),
+            title_links: Default::default(),
         };
         let mut broken_page_links = vec![];
         let parser = pulldown_cmark::Parser::new_with_broken_link_callback(
             &md,
-            pulldown_cmark::Options::empty(),
+            config.options(),
             Some(|link: pulldown_cmark::BrokenLink<'_>| {
                 if link.reference.as_bytes() == b"/" {
                     broken_page_links.push(PageId::root());
@@ -33,14 +48,74 @@ impl PageMeta {
         );
         let parser = pulldown_cmark::TextMergeStream::new(parser);
         let mut page_links = vec![];
+        let mut in_code_block = false;
+        let mut prev_char = None;
+        // the text of the first H1
+        let mut h1_text = None::<String>;
         let mut in_h1 = false;
+        let mut in_link = false;
+        // the first line of the first top-level paragraph or heading (the fallback title)
+        let mut first_line = None::<String>;
+        let mut in_first_line = false;
+        // the depth of the enclosing blocks and inlines
+        let mut depth = 0_usize;
         for event in parser {
+            match &event {
+                pulldown_cmark::Event::Start(
+                    pulldown_cmark::Tag::Paragraph | pulldown_cmark::Tag::Heading { .. },
+                ) if config.first_line_title && depth == 0 && first_line.is_none() => {
+                    in_first_line = true;
+                    first_line = Some(String::new());
+                }
+                pulldown_cmark::Event::End(
+                    pulldown_cmark::TagEnd::Paragraph | pulldown_cmark::TagEnd::Heading(_),
+                ) if depth == 1 => {
+                    in_first_line = false;
+                    // try the next block if the block has no text
+                    first_line = first_line
+                        .take()
+                        .map(|it| it.trim().to_owned())
+                        .filter(|it| !it.is_empty());
+                }
+                pulldown_cmark::Event::HardBreak | pulldown_cmark::Event::SoftBreak => {
+                    in_first_line = false;
+                }
+                _ => {
+                    if in_first_line && let Some(first_line) = first_line.as_mut() {
+                        push_plain_text(first_line, &event);
+                    }
+                }
+            }
+            let char_before = prev_char;
+            prev_char = last_inline_char(prev_char, &event);
+            match &event {
+                pulldown_cmark::Event::Start(_) => depth += 1,
+                pulldown_cmark::Event::End(_) => depth -= 1,
+                _ => { /* not a container */ }
+            }
             match event {
+                pulldown_cmark::Event::Start(pulldown_cmark::Tag::CodeBlock(_)) => {
+                    in_code_block = true;
+                }
+                pulldown_cmark::Event::End(pulldown_cmark::TagEnd::CodeBlock) => {
+                    in_code_block = false;
+                }
+                pulldown_cmark::Event::Start(pulldown_cmark::Tag::Image { .. }) => {
+                    in_link = true;
+                }
+                pulldown_cmark::Event::End(
+                    pulldown_cmark::TagEnd::Image | pulldown_cmark::TagEnd::Link,
+                ) => {
+                    in_link = false;
+                }
                 pulldown_cmark::Event::Start(pulldown_cmark::Tag::Heading {
                     level: pulldown_cmark::HeadingLevel::H1,
                     ..
                 }) => {
-                    in_h1 = true;
+                    in_h1 = page_meta.title.is_none();
+                    if in_h1 {
+                        h1_text = Some(String::new());
+                    }
                 }
                 pulldown_cmark::Event::Start(pulldown_cmark::Tag::Link {
                     link_type,
@@ -48,12 +123,17 @@ impl PageMeta {
                     title: _,
                     id: _,
                 }) => {
+                    in_link = true;
                     match link_type {
                         pulldown_cmark::LinkType::Inline
                         | pulldown_cmark::LinkType::Reference
                         | pulldown_cmark::LinkType::Collapsed
                         | pulldown_cmark::LinkType::Shortcut => {
-                            if let Some(stripped) = dest_url.strip_prefix('/') {
+                            if let Some(title) = dest_url.strip_prefix("/titles/") {
+                                if let Some(title) = crate::util::percent_decode(title) {
+                                    page_meta.title_links.insert(title);
+                                }
+                            } else if let Some(stripped) = dest_url.strip_prefix('/') {
                                 if stripped.is_empty() {
                                     page_links.push(PageId::root());
                                 } else {
@@ -70,27 +150,50 @@ impl PageMeta {
                         | pulldown_cmark::LinkType::CollapsedUnknown
                         | pulldown_cmark::LinkType::ShortcutUnknown
                         | pulldown_cmark::LinkType::Autolink
-                        | pulldown_cmark::LinkType::Email
-                        | pulldown_cmark::LinkType::WikiLink { .. } => {
+                        | pulldown_cmark::LinkType::Email => {
                             // do nothing
                         }
+                        pulldown_cmark::LinkType::WikiLink { .. } => {
+                            let target = dest_url.trim();
+                            match <PageId as std::str::FromStr>::from_str(target) {
+                                Err(_) => {
+                                    page_meta.title_links.insert(target.to_owned());
+                                }
+                                Ok(page_id) => {
+                                    page_links.push(page_id);
+                                }
+                            }
+                        }
                     }
                 }
                 pulldown_cmark::Event::Text(text) => {
-                    if in_h1 {
-                        match page_meta.title {
-                            None => {
-                                page_meta.title = Some(text.to_string());
-                            }
-                            Some(_) => {
-                                // do nothing
-                            }
+                    if !in_code_block && !in_link {
+                        for range in hashtags(&text, char_before) {
+                            page_meta
+                                .tags
+                                .insert(text[range.start + 1..range.end].to_owned());
                         }
                     }
+                    if in_h1 && let Some(h1_text) = h1_text.as_mut() {
+                        h1_text.push_str(&text);
+                    }
+                }
+                pulldown_cmark::Event::Code(_)
+                | pulldown_cmark::Event::HardBreak
+                | pulldown_cmark::Event::SoftBreak => {
+                    if in_h1 && let Some(h1_text) = h1_text.as_mut() {
+                        push_plain_text(h1_text, &event);
+                    }
                 }
                 pulldown_cmark::Event::End(pulldown_cmark::TagEnd::Heading(
                     pulldown_cmark::HeadingLevel::H1,
                 )) => {
+                    if in_h1 {
+                        page_meta.title = h1_text
+                            .take()
+                            .map(|it| it.trim().to_owned())
+                            .filter(|it| !it.is_empty());
+                    }
                     in_h1 = false;
                 }
                 _ => { /* ignore other events */ }
@@ -101,9 +204,101 @@ impl PageMeta {
             .into_iter()
             .chain(page_links.into_iter())
             .collect::<std::collections::BTreeSet<PageId>>();
+        if page_meta.title.is_none() {
+            page_meta.title = first_line;
+        }
+        page_meta.tags.extend(
+            page_meta
+                .properties
+                .tags
+                .iter()
+                .map(|tag| tag.trim_start_matches('#').to_owned())
+                .filter(|tag| !tag.is_empty()),
+        );
 
         page_meta
     }
+
+    /// Returns the title and the aliases declared in the front matter, by which the page is
+    /// found in the title index.
+    pub fn titles(&self) -> std::collections::BTreeSet<String> {
+        self.title
+            .iter()
+            .chain(&self.properties.aliases)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Appends the plain text of the inline `event` (text, code spans and line breaks) to `text`.
+fn push_plain_text(text: &mut String, event: &pulldown_cmark::Event<'_>) {
+    match event {
+        pulldown_cmark::Event::Code(s) | pulldown_cmark::Event::Text(s) => text.push_str(s),
+        pulldown_cmark::Event::HardBreak | pulldown_cmark::Event::SoftBreak => text.push(' '),
+        _ => { /* ignore other events */ }
+    }
+}
+
+/// Returns the last character of the inline content up to and including `event`, where `prev`
+/// is the one before `event`.
+///
+/// Inline markup such as emphasis and links is transparent, so in `**b**#tag` the `#` follows
+/// `b`. Block boundaries and line breaks return `None`.
+pub(crate) fn last_inline_char(
+    prev: Option<char>,
+    event: &pulldown_cmark::Event<'_>,
+) -> Option<char> {
+    match event {
+        pulldown_cmark::Event::Text(s)
+        | pulldown_cmark::Event::Code(s)
+        | pulldown_cmark::Event::InlineHtml(s)
+        | pulldown_cmark::Event::InlineMath(s) => s.chars().next_back().or(prev),
+        pulldown_cmark::Event::Start(
+            pulldown_cmark::Tag::Emphasis
+            | pulldown_cmark::Tag::Strong
+            | pulldown_cmark::Tag::Strikethrough
+            | pulldown_cmark::Tag::Superscript
+            | pulldown_cmark::Tag::Subscript
+            | pulldown_cmark::Tag::Link { .. }
+            | pulldown_cmark::Tag::Image { .. },
+        )
+        | pulldown_cmark::Event::End(
+            pulldown_cmark::TagEnd::Emphasis
+            | pulldown_cmark::TagEnd::Strong
+            | pulldown_cmark::TagEnd::Strikethrough
+            | pulldown_cmark::TagEnd::Superscript
+            | pulldown_cmark::TagEnd::Subscript
+            | pulldown_cmark::TagEnd::Link
+            | pulldown_cmark::TagEnd::Image,
+        ) => prev,
+        _ => None,
+    }
+}
+
+/// Returns the byte ranges of the `#tag`s (including `#`) in `text`, where `prev` is the
+/// character before `text` in the same block (see [`last_inline_char`]).
+///
+/// A tag starts with `#` at the beginning of a block or after a whitespace, and consists of
+/// alphanumeric characters, `_`, `-` and `/`. Numbers such as `#1` are not tags.
+pub(crate) fn hashtags(text: &str, prev: Option<char>) -> Vec<std::ops::Range<usize>> {
+    let is_tag_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '/');
+    let mut ranges = vec![];
+    let mut prev = prev;
+    for (start, c) in text.char_indices() {
+        let at_boundary = prev.is_none_or(char::is_whitespace);
+        prev = Some(c);
+        if c != '#' || !at_boundary {
+            continue;
+        }
+        let name = &text[start + 1..];
+        let name = &name[..name.find(|c| !is_tag_char(c)).unwrap_or(name.len())];
+        let name = name.trim_end_matches(['-', '/']);
+        if name.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        ranges.push(start..start + 1 + name.len());
+    }
+    ranges
 }
 
 #[cfg(test)]
@@ -113,16 +308,136 @@ mod tests {
     #[test]
     fn test_from_markdown_title_field() {
         let md = "# Title\n\nSome content.";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.title, Some("Title".to_string()));
 
         let md = "No title here.";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.title, None);
 
         let md = "# First Title\n\n# Second Title";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.title, Some("First Title".to_string()));
+
+        // inline elements
+        let md = "# Using `serde` in *Rust* with [links](/README) and [[Wiki]]";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(
+            page_meta.title,
+            Some("Using serde in Rust with links and Wiki".to_string())
+        );
+
+        // setext heading with a line break
+        let md = "First\nSecond\n===";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title, Some("First Second".to_string()));
+
+        // empty H1
+        let md = "#\n\n# Title";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title, Some("Title".to_string()));
+    }
+
+    #[test]
+    fn test_from_markdown_title_field_first_line() {
+        let config = crate::config::ConfigMarkdown {
+            first_line_title: true,
+            ..Default::default()
+        };
+
+        let md = "\n[ref]: /README\n\n## **Bold** `code`\n\nSome content.";
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("Bold code".to_string()));
+
+        let md = "---\ntags: [a]\n---\nNo title here.\n\n# Title";
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("Title".to_string()));
+
+        let md = "---\ntags: [a]\n---\nNo title here.\nSecond line.";
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("No title here.".to_string()));
+
+        // code blocks and lists are skipped
+        let md = "```\ncode\n```\n\n    indented\n\n- item\n\n> quote\n\nParagraph *text*\n";
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("Paragraph text".to_string()));
+
+        // an empty heading is skipped
+        let md = "##\n\nParagraph";
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("Paragraph".to_string()));
+
+        let page_meta = PageMeta::from_markdown("", &config);
+        assert_eq!(page_meta.title, None);
+
+        let page_meta = PageMeta::from_markdown("```\ncode\n```\n", &config);
+        assert_eq!(page_meta.title, None);
+    }
+
+    #[test]
+    fn test_from_markdown_properties_field() {
+        let md = "---\ntags: [rust]\n---\n# Title\n\nSome content.";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.properties.tags, vec!["rust"]);
+        assert_eq!(page_meta.title, Some("Title".to_string()));
+
+        // the front matter is not a setext heading
+        let md = "---\ntitle: Foo\n---\nSome content.";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(
+            page_meta.properties.custom.get("title"),
+            Some(&serde_json::json!("Foo"))
+        );
+        assert_eq!(page_meta.title, None);
+    }
+
+    #[test]
+    fn test_from_markdown_tags_field() {
+        fn set<I>(iter: I) -> std::collections::BTreeSet<String>
+        where
+            I: IntoIterator<Item = &'static str>,
+        {
+            iter.into_iter()
+                .map(str::to_owned)
+                .collect::<std::collections::BTreeSet<String>>()
+        }
+
+        let md = "# Title #draft\n\n#rust and #日本語, #a/b-c.\n\n- #list-item";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(
+            page_meta.tags,
+            set(["a/b-c", "draft", "list-item", "rust", "日本語"])
+        );
+
+        // code, links and numbers
+        let md =
+            "`#code`\n\n```\n#block\n```\n\n[#link](/README) ![#alt](/images/a.png) issue#1 #1 ##h";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert!(page_meta.tags.is_empty());
+
+        // after inline markup
+        let md = "**b**#strong `x`#code [l](/README)#link *e* #ok\n#line";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.tags, set(["line", "ok"]));
+
+        // front matter
+        let md = "---\ntags: [rust, '#serde']\n---\n#rust";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.tags, set(["rust", "serde"]));
+    }
+
+    #[test]
+    fn test_from_markdown_config() {
+        let md = "# Title {#title}\n\nSome content.";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title, Some("Title {#title}".to_string()));
+
+        let config = crate::config::ConfigMarkdown {
+            heading_attributes: true,
+            ..Default::default()
+        };
+        let page_meta = PageMeta::from_markdown(md, &config);
+        assert_eq!(page_meta.title, Some("Title".to_string()));
     }
 
     #[test]
@@ -141,47 +456,47 @@ mod tests {
 
         // inline link
         let md = "[foo](/19700102T151617Z)";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // reference link
         let md = "[foo][bar]\n\n[bar]: /19700102T151617Z";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // reference link (broken)
         let md = "[foo][19700102T151617Z]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // collapsed link
         let md = "[foo][]\n\n[foo]: /19700102T151617Z";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // collapsed link (broken)
         let md = "[19700102T151617Z][]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // shortcut link
         let md = "[foo]\n\n[foo]: /19700102T151617Z";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // shortcut link (broken)
         let md = "[19700102T151617Z]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // (duplicate)
         let md = "[19700102T151617Z]\n\n[19700102T151617Z]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("19700102T151617Z")?]));
 
         // (two different)
         let md = "[19700102T151617Z]\n\n[19710102T151617Z]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(
             page_meta.links,
             set([id("19700102T151617Z")?, id("19710102T151617Z")?])
@@ -190,6 +505,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_markdown_title_links_field() -> anyhow::Result<()> {
+        fn set<I>(iter: I) -> std::collections::BTreeSet<String>
+        where
+            I: IntoIterator<Item = &'static str>,
+        {
+            iter.into_iter()
+                .map(str::to_owned)
+                .collect::<std::collections::BTreeSet<String>>()
+        }
+
+        let md = "[[Some Title]]";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title_links, set(["Some Title"]));
+        assert!(page_meta.links.is_empty());
+
+        // with label
+        let md = "[[Some Title|label]] and [[ Other Title ]]";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title_links, set(["Other Title", "Some Title"]));
+
+        // ID
+        let md = "[[19700102T151617Z]]";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert!(page_meta.title_links.is_empty());
+        assert_eq!(
+            page_meta.links,
+            [<PageId as std::str::FromStr>::from_str("19700102T151617Z")?]
+                .into_iter()
+                .collect::<std::collections::BTreeSet<PageId>>()
+        );
+
+        // `/titles/{title}`
+        let md = "[a](/titles/Some%20Title) [b][ref]\n\n[ref]: /titles/%E6%97%A5%E6%9C%AC";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert_eq!(page_meta.title_links, set(["Some Title", "日本"]));
+        assert!(page_meta.links.is_empty());
+
+        // code span
+        let md = "`[[Some Title]]`";
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
+        assert!(page_meta.title_links.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_markdown_links_field_root() -> anyhow::Result<()> {
         fn id(s: &str) -> anyhow::Result<PageId> {
@@ -206,80 +567,80 @@ mod tests {
 
         // inline link
         let md = "[foo](/README)";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[foo](/)";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // reference link
         let md = "[foo][bar]\n\n[bar]: /README";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[foo][bar]\n\n[bar]: /";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // reference link (broken)
         let md = "[foo][README]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[foo][/]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // collapsed link
         let md = "[foo][]\n\n[foo]: /README";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[foo][]\n\n[foo]: /";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // collapsed link (broken)
         let md = "[README][]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[/][]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // shortcut link
         let md = "[foo]\n\n[foo]: /README";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[foo]\n\n[foo]: /";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // shortcut link (broken)
         let md = "[README]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[/]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // (duplicate)
         let md = "[README]\n\n[README]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[/]\n\n[/]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
         let md = "[README]\n\n[/]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(page_meta.links, set([id("README")?]));
 
         // (two different)
         let md = "[19700102T151617Z]\n\n[README]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(
             page_meta.links,
             set([id("19700102T151617Z")?, id("README")?])
         );
         let md = "[19700102T151617Z]\n\n[/]";
-        let page_meta = PageMeta::from_markdown(md);
+        let page_meta = PageMeta::from_markdown(md, &Default::default());
         assert_eq!(
             page_meta.links,
             set([id("19700102T151617Z")?, id("README")?])
@@ -0,0 +1,201 @@
+/// The properties declared in the front matter of a page.
+///
+/// The front matter is a YAML (`---`) or TOML (`+++`) table at the top of the page:
+///
+/// ```markdown
+/// ---
+/// tags: [rust, serde]
+/// aliases: [Serde]
+/// created: 2025-12-24
+/// status: draft
+/// ---
+///
+/// # Using serde in Rust
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct PageProperties {
+    pub aliases: Vec<String>,
+    pub created: Option<String>,
+    /// The keys other than the well-known ones
+    pub custom: std::collections::BTreeMap<String, serde_json::Value>,
+    pub tags: Vec<String>,
+    pub updated: Option<String>,
+}
+
+impl PageProperties {
+    /// Splits `md` into the properties and the rest of the page.
+    ///
+    /// If `md` has no front matter, or it is not a valid YAML/TOML table, the properties are
+    /// empty and `md` is returned as is.
+    pub fn split_front_matter(md: &str) -> (Self, &str) {
+        Self::try_split_front_matter(md).unwrap_or_else(|| (Self::default(), md))
+    }
+
+    /// Returns the values of the property `key` as strings. Lists are flattened.
+    pub fn values(&self, key: &str) -> Vec<String> {
+        match key {
+            "aliases" => self.aliases.clone(),
+            "created" => self.created.iter().cloned().collect(),
+            "tags" => self.tags.clone(),
+            "updated" => self.updated.iter().cloned().collect(),
+            _ => self.custom.get(key).map(scalars).unwrap_or_default(),
+        }
+    }
+
+    fn try_split_front_matter(md: &str) -> Option<(Self, &str)> {
+        let (delimiter, rest) =
+            [("---", "---"), ("+++", "+++")]
+                .into_iter()
+                .find_map(|(open, close)| {
+                    let rest = md.strip_prefix(open)?;
+                    let rest = rest
+                        .strip_prefix("\r\n")
+                        .or_else(|| rest.strip_prefix('\n'))?;
+                    Some((close, rest))
+                })?;
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            let trimmed = line.trim_end();
+            if trimmed == delimiter || (delimiter == "---" && trimmed == "...") {
+                let front_matter = &rest[..offset];
+                let body = &rest[offset + line.len()..];
+                let table = if delimiter == "---" {
+                    serde_norway::from_str::<serde_json::Value>(front_matter).ok()?
+                } else {
+                    toml_to_json(toml::Value::Table(
+                        toml::from_str::<toml::Table>(front_matter).ok()?,
+                    ))
+                };
+                let serde_json::Value::Object(table) = table else {
+                    return None;
+                };
+                return Some((Self::from_table(table), body));
+            }
+            offset += line.len();
+        }
+        None
+    }
+
+    fn from_table(table: serde_json::Map<String, serde_json::Value>) -> Self {
+        let mut properties = Self::default();
+        for (key, value) in table {
+            match key.as_str() {
+                "aliases" => properties.aliases = scalars(&value),
+                "created" => properties.created = scalars(&value).into_iter().next(),
+                "tags" => properties.tags = scalars(&value),
+                "updated" => properties.updated = scalars(&value).into_iter().next(),
+                _ => {
+                    properties.custom.insert(key, value);
+                }
+            }
+        }
+        properties
+    }
+}
+
+/// Returns the non-empty scalar values in `value` (or in the list `value`) as strings.
+fn scalars(value: &serde_json::Value) -> Vec<String> {
+    let scalar = |value: &serde_json::Value| match value {
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) => Some(s.trim().to_owned()),
+        serde_json::Value::Array(_) | serde_json::Value::Null | serde_json::Value::Object(_) => {
+            None
+        }
+    };
+    let values = match value {
+        serde_json::Value::Array(values) => values.iter().filter_map(scalar).collect(),
+        _ => scalar(value).into_iter().collect::<Vec<String>>(),
+    };
+    values.into_iter().filter(|it| !it.is_empty()).collect()
+}
+
+/// Converts `value` to JSON. Dates and times are converted to strings.
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(toml_to_json).collect())
+        }
+        toml::Value::Boolean(b) => serde_json::Value::Bool(b),
+        toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        toml::Value::Integer(i) => serde_json::Value::Number(i.into()),
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_front_matter_yaml() {
+        let md = "---\ntags: [rust, serde]\naliases: Serde\ncreated: 2025-12-24\nstatus: draft\nrating: 5\n---\n# Title\n";
+        let (properties, body) = PageProperties::split_front_matter(md);
+        assert_eq!(body, "# Title\n");
+        assert_eq!(
+            properties,
+            PageProperties {
+                aliases: vec!["Serde".to_owned()],
+                created: Some("2025-12-24".to_owned()),
+                custom: [
+                    ("rating".to_owned(), serde_json::json!(5)),
+                    ("status".to_owned(), serde_json::json!("draft")),
+                ]
+                .into_iter()
+                .collect(),
+                tags: vec!["rust".to_owned(), "serde".to_owned()],
+                updated: None,
+            }
+        );
+        assert_eq!(properties.values("tags"), vec!["rust", "serde"]);
+        assert_eq!(properties.values("rating"), vec!["5"]);
+        assert!(properties.values("unknown").is_empty());
+
+        // `...` closes the front matter
+        let (properties, body) = PageProperties::split_front_matter("---\ntags: a\n...\nbody");
+        assert_eq!(properties.tags, vec!["a"]);
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn test_split_front_matter_toml() {
+        let md = "+++\ntags = [\"rust\"]\nupdated = 2025-12-25T01:02:03Z\n[extra]\nkey = 1\n+++\r\n# Title\n";
+        let (properties, body) = PageProperties::split_front_matter(md);
+        assert_eq!(body, "# Title\n");
+        assert_eq!(properties.tags, vec!["rust"]);
+        assert_eq!(properties.updated, Some("2025-12-25T01:02:03Z".to_owned()));
+        assert_eq!(
+            properties.custom.get("extra"),
+            Some(&serde_json::json!({"key": 1}))
+        );
+    }
+
+    #[test]
+    fn test_split_front_matter_none() {
+        for md in [
+            "# Title\n",
+            // not at the top
+            "\n---\ntags: a\n---\n",
+            // not closed
+            "---\ntags: a\n",
+            // not a table
+            "---\nfoo\n---\n",
+            // a thematic break and a setext heading
+            "---\nfoo: [\n---\n",
+        ] {
+            assert_eq!(
+                PageProperties::split_front_matter(md),
+                (PageProperties::default(), md)
+            );
+        }
+    }
+}
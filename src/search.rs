@@ -0,0 +1,490 @@
+mod query;
+mod snippet;
+
+pub(crate) use self::query::Document;
+pub(crate) use self::query::Query;
+pub(crate) use self::snippet::SnippetFragment;
+pub(crate) use self::snippet::snippets;
+
+/// Returns the first non-empty line of `content` that contains any of `keywords`.
+///
+/// `keywords` must be normalized.
+pub(crate) fn find_context_line<'a>(
+    content: &'a str,
+    keywords: &[String],
+    config: &crate::config::ConfigSearch,
+) -> Option<&'a str> {
+    content.lines().map(str::trim).find(|line| {
+        !line.is_empty() && {
+            let line = normalize(line, config);
+            keywords
+                .iter()
+                .any(|keyword| line.contains(keyword.as_str()))
+        }
+    })
+}
+
+/// Normalizes `s` for matching.
+///
+/// NFKC folds full-width alphanumerics into half-width ones and half-width katakana into
+/// full-width ones. The result is lowercased and, if enabled, katakana is folded into hiragana.
+pub(crate) fn normalize(s: &str, config: &crate::config::ConfigSearch) -> String {
+    unicode_normalization::UnicodeNormalization::nfkc(s)
+        .flat_map(char::to_lowercase)
+        .map(|c| if config.kana_folding { fold_kana(c) } else { c })
+        .collect::<String>()
+}
+
+/// An inverted index from terms to the pages that contain them.
+///
+/// The normalized content of each page is also kept to verify matches. The terms, the sets of
+/// pages and the contents are shared between clones, and only the sets of the terms of a changed
+/// page are copied on write.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TermIndex {
+    config: crate::config::ConfigSearch,
+    postings: std::collections::BTreeMap<
+        std::sync::Arc<str>,
+        std::sync::Arc<std::collections::BTreeSet<crate::page_id::PageId>>,
+    >,
+    texts: std::collections::BTreeMap<crate::page_id::PageId, std::sync::Arc<str>>,
+}
+
+impl TermIndex {
+    pub(crate) fn new(config: crate::config::ConfigSearch) -> Self {
+        Self {
+            config,
+            postings: Default::default(),
+            texts: Default::default(),
+        }
+    }
+
+    /// Returns the pages that may match `query`, or `None` if the query cannot narrow them down.
+    pub(crate) fn candidates(
+        &self,
+        query: &Query,
+    ) -> Option<std::collections::BTreeSet<crate::page_id::PageId>> {
+        match query {
+            Query::And(queries) => queries
+                .iter()
+                .filter_map(|it| self.candidates(it))
+                .reduce(|a, b| a.intersection(&b).cloned().collect()),
+            Query::Or(queries) => queries
+                .iter()
+                .map(|it| self.candidates(it))
+                .collect::<Option<Vec<_>>>()
+                .map(|sets| sets.into_iter().flatten().collect()),
+            Query::Phrase(s) | Query::Word(s) => tokenize(s, &self.config)
+                .map(|token| {
+                    // the terms that start with `token`
+                    self.postings
+                        .range::<str, _>((
+                            std::ops::Bound::Included(token.as_str()),
+                            std::ops::Bound::Unbounded,
+                        ))
+                        .take_while(|(term, _)| term.starts_with(token.as_str()))
+                        .flat_map(|(_, page_ids)| page_ids.iter().cloned())
+                        .collect::<std::collections::BTreeSet<crate::page_id::PageId>>()
+                })
+                .reduce(|a, b| a.intersection(&b).cloned().collect()),
+            Query::Created(_)
+            | Query::LinkedFrom(_)
+            | Query::Links(_)
+            | Query::Not(_)
+            | Query::Property(_, _)
+            | Query::Title(_) => None,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &crate::config::ConfigSearch {
+        &self.config
+    }
+
+    pub(crate) fn insert(&mut self, page_id: &crate::page_id::PageId, content: &str) {
+        self.remove(page_id);
+
+        let text = normalize(content, &self.config);
+        for term in index_terms(&text, &self.config) {
+            std::sync::Arc::make_mut(self.postings.entry(std::sync::Arc::from(term)).or_default())
+                .insert(page_id.clone());
+        }
+        self.texts
+            .insert(page_id.clone(), std::sync::Arc::from(text));
+    }
+
+    pub(crate) fn remove(&mut self, page_id: &crate::page_id::PageId) {
+        let Some(text) = self.texts.remove(page_id) else {
+            return;
+        };
+        for term in index_terms(&text, &self.config) {
+            if let Some(set) = self.postings.get_mut(term.as_str()) {
+                std::sync::Arc::make_mut(set).remove(page_id);
+                if set.is_empty() {
+                    self.postings.remove(term.as_str());
+                }
+            }
+        }
+    }
+
+    /// Scores `page_ids` against `keywords` with BM25 and returns them in descending order of score.
+    ///
+    /// A keyword in the title of a page adds to the score.
+    pub(crate) fn rank<'a, I, F>(
+        &self,
+        page_ids: I,
+        keywords: &[String],
+        title: F,
+    ) -> Vec<(crate::page_id::PageId, f64)>
+    where
+        I: IntoIterator<Item = crate::page_id::PageId>,
+        F: Fn(&crate::page_id::PageId) -> Option<&'a str>,
+    {
+        const B: f64 = 0.75;
+        const K1: f64 = 1.2;
+        const TITLE_WEIGHT: f64 = 1.0;
+
+        let n = self.texts.len() as f64;
+        let avg_len = self.texts.values().map(|it| it.len()).sum::<usize>() as f64 / n.max(1.0);
+        let idfs = keywords
+            .iter()
+            .map(|keyword| {
+                let df = self
+                    .candidates(&Query::Word(keyword.clone()))
+                    .map(|page_ids| {
+                        page_ids
+                            .iter()
+                            .filter(|page_id| {
+                                self.text(page_id)
+                                    .is_some_and(|text| text.contains(keyword.as_str()))
+                            })
+                            .count()
+                    })
+                    .unwrap_or(0) as f64;
+                (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+            })
+            .collect::<Vec<f64>>();
+
+        let mut ranked = page_ids
+            .into_iter()
+            .map(|page_id| {
+                let text = self.text(&page_id).unwrap_or_default();
+                let len = text.len() as f64;
+                let title = title(&page_id).map(|it| normalize(it, &self.config));
+                let score = keywords
+                    .iter()
+                    .zip(idfs.iter())
+                    .map(|(keyword, idf)| {
+                        let tf = text.matches(keyword.as_str()).count() as f64;
+                        let tf =
+                            tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * len / avg_len.max(1.0)));
+                        let title = title
+                            .as_deref()
+                            .is_some_and(|it| it.contains(keyword.as_str()));
+                        idf * (tf + if title { TITLE_WEIGHT } else { 0.0 })
+                    })
+                    .sum::<f64>();
+                (page_id, score)
+            })
+            .collect::<Vec<(crate::page_id::PageId, f64)>>();
+        ranked.sort_by(|(a_id, a_score), (b_id, b_score)| {
+            b_score.total_cmp(a_score).then_with(|| a_id.cmp(b_id))
+        });
+        ranked
+    }
+
+    /// Returns the normalized content of the page.
+    pub(crate) fn text(&self, page_id: &crate::page_id::PageId) -> Option<&str> {
+        self.texts.get(page_id).map(|it| &**it)
+    }
+}
+
+/// Splits `s` into normalized terms.
+///
+/// Runs of CJK characters have no word boundaries, so they are split into character bigrams
+/// (a run of a single character becomes a unigram). Other runs of alphanumeric characters
+/// become one term each.
+pub(crate) fn tokenize(
+    s: &str,
+    config: &crate::config::ConfigSearch,
+) -> impl Iterator<Item = String> {
+    tokenize_with(s, config, false)
+}
+
+/// Splits `s` into the terms to index.
+///
+/// In addition to the terms by [`tokenize`], the last character of each CJK run is indexed as
+/// a unigram, so that any single CJK character in the query is found by a prefix lookup.
+fn index_terms(s: &str, config: &crate::config::ConfigSearch) -> impl Iterator<Item = String> {
+    tokenize_with(s, config, true)
+}
+
+fn tokenize_with(
+    s: &str,
+    config: &crate::config::ConfigSearch,
+    last_cjk_unigram: bool,
+) -> impl Iterator<Item = String> {
+    let s = normalize(s, config);
+    let mut terms = vec![];
+    let mut word = String::new();
+    let mut cjk = vec![];
+    let flush_cjk = |terms: &mut Vec<String>, cjk: &mut Vec<char>| {
+        match cjk.as_slice() {
+            [] => {}
+            [c] => terms.push(c.to_string()),
+            [.., last] => {
+                terms.extend(cjk.windows(2).map(|it| it.iter().collect::<String>()));
+                if last_cjk_unigram {
+                    terms.push(last.to_string());
+                }
+            }
+        }
+        cjk.clear();
+    };
+    for c in s.chars() {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+            cjk.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk(&mut terms, &mut cjk);
+            word.push(c);
+        } else {
+            flush_cjk(&mut terms, &mut cjk);
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+        }
+    }
+    flush_cjk(&mut terms, &mut cjk);
+    if !word.is_empty() {
+        terms.push(word);
+    }
+    terms.into_iter()
+}
+
+fn fold_kana(c: char) -> char {
+    match c {
+        // katakana (ァ..=ヶ, ヽ, ヾ) -> hiragana (ぁ..=ゖ, ゝ, ゞ)
+        '\u{30A1}'..='\u{30F6}' | '\u{30FD}' | '\u{30FE}' => {
+            char::from_u32(c as u32 - 0x60).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        // CJK Symbols and Punctuation (々, 〆, 〇)
+        '\u{3005}'..='\u{3007}'
+        // Hiragana
+        | '\u{3041}'..='\u{309F}'
+        // Katakana
+        | '\u{30A1}'..='\u{30FF}'
+        // CJK Unified Ideographs Extension A
+        | '\u{3400}'..='\u{4DBF}'
+        // CJK Unified Ideographs
+        | '\u{4E00}'..='\u{9FFF}'
+        // Hangul Syllables
+        | '\u{AC00}'..='\u{D7AF}'
+        // CJK Compatibility Ideographs
+        | '\u{F900}'..='\u{FAFF}'
+        // CJK Unified Ideographs Extension B..
+        | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_context_line() {
+        let config = crate::config::ConfigSearch::default();
+        let content = "# Title\n\n  First line.\nSecond LINE.\n";
+        let keywords = |s: &[&str]| s.iter().map(|it| it.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            find_context_line(content, &keywords(&["title"]), &config),
+            Some("# Title")
+        );
+        assert_eq!(
+            find_context_line(content, &keywords(&["line"]), &config),
+            Some("First line.")
+        );
+        assert_eq!(
+            find_context_line(content, &keywords(&["second"]), &config),
+            Some("Second LINE.")
+        );
+        assert_eq!(
+            find_context_line(content, &keywords(&["notfound", "second"]), &config),
+            Some("Second LINE.")
+        );
+        assert_eq!(
+            find_context_line(content, &keywords(&["notfound"]), &config),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let config = crate::config::ConfigSearch::default();
+        // full-width alphanumerics -> half-width
+        assert_eq!(normalize("ＲＵＳＴ１２３", &config), "rust123");
+        // half-width katakana -> full-width
+        assert_eq!(normalize("ｶﾀｶﾅ ｶﾞ", &config), "カタカナ ガ");
+        // no kana folding by default
+        assert_eq!(normalize("カタカナ", &config), "カタカナ");
+
+        let config = crate::config::ConfigSearch { kana_folding: true };
+        assert_eq!(normalize("カタカナ", &config), "かたかな");
+        assert_eq!(normalize("ｶﾞｰﾃﾞﾝ", &config), "がーでん");
+    }
+
+    #[test]
+    fn test_term_index() -> anyhow::Result<()> {
+        let page1_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000000Z")?;
+        let page2_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000001Z")?;
+        let mut term_index = TermIndex::default();
+        term_index.insert(&page1_id, "# Rust\n\nThis is a sample page.");
+        term_index.insert(&page2_id, "# Pages\n\nSee [20251224T000000Z].");
+
+        let set = |ids: &[&crate::page_id::PageId]| {
+            ids.iter()
+                .map(|it| (*it).clone())
+                .collect::<std::collections::BTreeSet<crate::page_id::PageId>>()
+        };
+        let search = |term_index: &TermIndex, q: &str| {
+            term_index
+                .candidates(&Query::parse(q, term_index.config()))
+                .unwrap_or_else(|| term_index.texts.keys().cloned().collect())
+        };
+        // single keyword
+        assert_eq!(search(&term_index, "rust"), set(&[&page1_id]));
+        // ignore case
+        assert_eq!(search(&term_index, "RUST"), set(&[&page1_id]));
+        // prefix of term
+        assert_eq!(search(&term_index, "page"), set(&[&page1_id, &page2_id]));
+        assert_eq!(search(&term_index, "ages"), set(&[]));
+        // multiple keywords
+        assert_eq!(search(&term_index, "page\u{3000}sample"), set(&[&page1_id]));
+        assert_eq!(search(&term_index, "20251224T000000Z"), set(&[&page2_id]));
+        // not found
+        assert_eq!(search(&term_index, "notfound page"), set(&[]));
+        // no keywords
+        assert_eq!(search(&term_index, ""), set(&[&page1_id, &page2_id]));
+
+        // a clone shares the sets of the terms that are not changed
+        let snapshot = term_index.clone();
+        term_index.insert(&page1_id, "# Pages");
+        assert!(std::sync::Arc::ptr_eq(
+            &snapshot.postings["see"],
+            &term_index.postings["see"]
+        ));
+        assert!(!std::sync::Arc::ptr_eq(
+            &snapshot.postings["pages"],
+            &term_index.postings["pages"]
+        ));
+
+        // re-insert replaces the old terms
+        term_index.insert(&page1_id, "# Go");
+        assert_eq!(search(&term_index, "rust"), set(&[]));
+        assert_eq!(search(&term_index, "go"), set(&[&page1_id]));
+
+        term_index.remove(&page1_id);
+        assert_eq!(search(&term_index, "go"), set(&[]));
+        assert!(!term_index.postings.contains_key("go"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_index_rank() -> anyhow::Result<()> {
+        let page1_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000000Z")?;
+        let page2_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000001Z")?;
+        let page3_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000002Z")?;
+        let mut term_index = TermIndex::default();
+        term_index.insert(&page1_id, "# Go\n\nRust is mentioned once.");
+        term_index.insert(&page2_id, "# Rust\n\nRust, rust and rust.");
+        term_index.insert(&page3_id, "# C\n\nNothing here.");
+
+        let keywords = vec!["rust".to_owned()];
+        let titles = [(page1_id.clone(), "Go"), (page2_id.clone(), "Rust")]
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>();
+        let ranked = term_index.rank(
+            [page1_id.clone(), page2_id.clone(), page3_id.clone()],
+            &keywords,
+            |page_id| titles.get(page_id).copied(),
+        );
+        assert_eq!(
+            ranked.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>(),
+            vec![page2_id.clone(), page1_id.clone(), page3_id.clone()]
+        );
+        assert!(ranked[0].1 > ranked[1].1);
+        assert!(ranked[1].1 > 0.0);
+        assert_eq!(ranked[2].1, 0.0);
+
+        // no keywords
+        let ranked = term_index.rank([page2_id.clone(), page1_id.clone()], &[], |_| None);
+        assert_eq!(
+            ranked,
+            vec![(page1_id.clone(), 0.0), (page2_id.clone(), 0.0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_term_index_cjk() -> anyhow::Result<()> {
+        let page1_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000000Z")?;
+        let page2_id = <crate::page_id::PageId as std::str::FromStr>::from_str("20251224T000001Z")?;
+        let mut term_index = TermIndex::new(crate::config::ConfigSearch { kana_folding: true });
+        term_index.insert(&page1_id, "# 東京都の天気\n\nｷｮｳは晴れ。");
+        term_index.insert(&page2_id, "# 京都の天気\n\nRustの所有権");
+
+        let set = |ids: &[&crate::page_id::PageId]| {
+            ids.iter()
+                .map(|it| (*it).clone())
+                .collect::<std::collections::BTreeSet<crate::page_id::PageId>>()
+        };
+        let search = |term_index: &TermIndex, q: &str| {
+            term_index
+                .candidates(&Query::parse(q, term_index.config()))
+                .unwrap_or_else(|| term_index.texts.keys().cloned().collect())
+        };
+        assert_eq!(search(&term_index, "天気"), set(&[&page1_id, &page2_id]));
+        assert_eq!(search(&term_index, "東京"), set(&[&page1_id]));
+        assert_eq!(search(&term_index, "京都"), set(&[&page1_id, &page2_id]));
+        // single character at the start, middle and end of a run
+        assert_eq!(search(&term_index, "東"), set(&[&page1_id]));
+        assert_eq!(search(&term_index, "都"), set(&[&page1_id, &page2_id]));
+        assert_eq!(search(&term_index, "権"), set(&[&page2_id]));
+        // width and kana folding
+        assert_eq!(search(&term_index, "きょう"), set(&[&page1_id]));
+        assert_eq!(search(&term_index, "キョウ"), set(&[&page1_id]));
+        // mixed script
+        assert_eq!(search(&term_index, "rust 所有権"), set(&[&page2_id]));
+        assert_eq!(search(&term_index, "大阪"), set(&[]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let config = crate::config::ConfigSearch::default();
+        assert_eq!(
+            tokenize("# Hello, World!\n[foo](/20251224T000000Z)", &config).collect::<Vec<String>>(),
+            vec!["hello", "world", "foo", "20251224t000000z"]
+        );
+        assert_eq!(
+            tokenize("日本語の テキスト", &config).collect::<Vec<String>>(),
+            vec!["日本", "本語", "語の", "テキ", "キス", "スト"]
+        );
+        assert_eq!(
+            tokenize("Rustの所有権 字", &config).collect::<Vec<String>>(),
+            vec!["rust", "の所", "所有", "有権", "字"]
+        );
+        assert_eq!(
+            index_terms("Rustの所有権 字", &config).collect::<Vec<String>>(),
+            vec!["rust", "の所", "所有", "有権", "権", "字"]
+        );
+    }
+}
@@ -0,0 +1,404 @@
+/// A parsed search query.
+///
+/// - `foo bar` matches pages that contain both `foo` and `bar`
+/// - `"foo bar"` matches pages that contain the phrase `foo bar`
+/// - `-foo` matches pages that do not contain `foo`
+/// - `foo OR bar` matches pages that contain `foo` or `bar`
+/// - `title:foo` matches pages whose title or alias contains `foo`
+/// - `links:<ID>` matches pages that link to `<ID>`
+/// - `linkedfrom:<ID>` matches pages that `<ID>` links to
+/// - `created:2025-12` matches pages created in the period (derived from the ID)
+/// - `prop:status` matches pages that have the front matter property `status`
+/// - `prop:status=draft` matches pages whose property `status` (or any of its values) is `draft`
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Query {
+    And(Vec<Query>),
+    Created(String),
+    LinkedFrom(crate::page_id::PageId),
+    Links(crate::page_id::PageId),
+    Not(Box<Query>),
+    Or(Vec<Query>),
+    Phrase(String),
+    Property(String, Option<String>),
+    Title(String),
+    Word(String),
+}
+
+/// A page to match a [`Query`] against.
+pub(crate) struct Document<'a> {
+    pub(crate) backlinks: Option<&'a std::collections::BTreeSet<crate::page_id::PageId>>,
+    pub(crate) page_id: &'a crate::page_id::PageId,
+    pub(crate) page_meta: &'a crate::page_meta::PageMeta,
+    /// The normalized content of the page
+    pub(crate) text: &'a str,
+}
+
+impl Query {
+    /// Parses `s`. Words and phrases are normalized with `config`.
+    ///
+    /// Parsing never fails. Unknown fields are treated as plain words.
+    pub(crate) fn parse(s: &str, config: &crate::config::ConfigSearch) -> Self {
+        let mut groups: Vec<Vec<Query>> = vec![];
+        let mut or_pending = false;
+        for token in split(s) {
+            match token {
+                Token::Or => {
+                    or_pending = !groups.is_empty();
+                }
+                Token::Clause {
+                    negated,
+                    field,
+                    value,
+                    quoted,
+                } => {
+                    let query = clause(field.as_deref(), &value, quoted, config);
+                    let query = if negated {
+                        Query::Not(Box::new(query))
+                    } else {
+                        query
+                    };
+                    match groups.last_mut() {
+                        Some(group) if or_pending => group.push(query),
+                        _ => groups.push(vec![query]),
+                    }
+                    or_pending = false;
+                }
+            }
+        }
+        Query::And(
+            groups
+                .into_iter()
+                .map(|mut group| {
+                    if group.len() == 1 {
+                        group.remove(0)
+                    } else {
+                        Query::Or(group)
+                    }
+                })
+                .collect::<Vec<Query>>(),
+        )
+    }
+
+    /// Returns the words and phrases that a matching page may contain.
+    ///
+    /// Excluded words are not returned.
+    pub(crate) fn keywords(&self) -> Vec<String> {
+        match self {
+            Query::And(queries) | Query::Or(queries) => {
+                queries.iter().flat_map(Query::keywords).collect()
+            }
+            Query::Phrase(s) | Query::Word(s) => vec![s.clone()],
+            Query::Created(_)
+            | Query::LinkedFrom(_)
+            | Query::Links(_)
+            | Query::Not(_)
+            | Query::Property(_, _)
+            | Query::Title(_) => vec![],
+        }
+    }
+
+    pub(crate) fn matches(
+        &self,
+        document: &Document<'_>,
+        config: &crate::config::ConfigSearch,
+    ) -> bool {
+        match self {
+            Query::And(queries) => queries.iter().all(|it| it.matches(document, config)),
+            Query::Created(prefix) => document.page_id.created_at().is_some_and(|it| {
+                it.format("%Y-%m-%dT%H:%M:%SZ")
+                    .to_string()
+                    .starts_with(prefix)
+            }),
+            Query::LinkedFrom(page_id) => document
+                .backlinks
+                .is_some_and(|backlinks| backlinks.contains(page_id)),
+            Query::Links(page_id) => document.page_meta.links.contains(page_id),
+            Query::Not(query) => !query.matches(document, config),
+            Query::Or(queries) => queries.iter().any(|it| it.matches(document, config)),
+            Query::Phrase(s) | Query::Word(s) => document.text.contains(s.as_str()),
+            Query::Property(key, value) => {
+                let values = document.page_meta.properties.values(key);
+                match value {
+                    None => !values.is_empty(),
+                    Some(value) => values
+                        .iter()
+                        .any(|it| super::normalize(it, config) == *value),
+                }
+            }
+            Query::Title(s) => document
+                .page_meta
+                .titles()
+                .iter()
+                .any(|title| super::normalize(title, config).contains(s.as_str())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Clause {
+        negated: bool,
+        field: Option<String>,
+        value: String,
+        quoted: bool,
+    },
+    Or,
+}
+
+fn clause(
+    field: Option<&str>,
+    value: &str,
+    quoted: bool,
+    config: &crate::config::ConfigSearch,
+) -> Query {
+    let word = |value: &str| {
+        let value = super::normalize(value, config);
+        if quoted {
+            Query::Phrase(value)
+        } else {
+            Query::Word(value)
+        }
+    };
+    let page_id = |value: &str| <crate::page_id::PageId as std::str::FromStr>::from_str(value);
+    match field {
+        None => word(value),
+        Some("created") => Query::Created(value.to_owned()),
+        Some("linkedfrom") => match page_id(value) {
+            Ok(page_id) => Query::LinkedFrom(page_id),
+            Err(_) => word(&format!("linkedfrom:{value}")),
+        },
+        Some("links") => match page_id(value) {
+            Ok(page_id) => Query::Links(page_id),
+            Err(_) => word(&format!("links:{value}")),
+        },
+        Some("prop") => match value.split_once('=') {
+            None => Query::Property(value.to_owned(), None),
+            Some((key, value)) => {
+                Query::Property(key.to_owned(), Some(super::normalize(value, config)))
+            }
+        },
+        Some("title") => Query::Title(super::normalize(value, config)),
+        Some(field) => word(&format!("{field}:{value}")),
+    }
+}
+
+fn split(s: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&c) = chars.peek() else {
+            break;
+        };
+
+        let negated = c == '-' && {
+            chars.next();
+            true
+        };
+
+        let mut field = None;
+        let mut value = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                '"' if value.is_empty() => {
+                    quoted = true;
+                    for c in chars.by_ref() {
+                        if c == '"' {
+                            break;
+                        }
+                        value.push(c);
+                    }
+                    break;
+                }
+                ':' if field.is_none() && !value.is_empty() => {
+                    field = Some(std::mem::take(&mut value));
+                }
+                _ => value.push(c),
+            }
+        }
+
+        if !negated && !quoted && field.is_none() && value == "OR" {
+            tokens.push(Token::Or);
+            continue;
+        }
+        if value.is_empty() {
+            match (negated, field) {
+                (_, Some(field)) => value = format!("{field}:"),
+                (true, None) => value = "-".to_owned(),
+                (false, None) => continue,
+            }
+            tokens.push(Token::Clause {
+                negated: false,
+                field: None,
+                value,
+                quoted,
+            });
+            continue;
+        }
+        tokens.push(Token::Clause {
+            negated,
+            field,
+            value,
+            quoted,
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(s: &str) -> anyhow::Result<crate::page_id::PageId> {
+        <crate::page_id::PageId as std::str::FromStr>::from_str(s)
+    }
+
+    fn word(s: &str) -> Query {
+        Query::Word(s.to_owned())
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let config = crate::config::ConfigSearch::default();
+        let parse = |s: &str| Query::parse(s, &config);
+
+        assert_eq!(parse(""), Query::And(vec![]));
+        assert_eq!(
+            parse("Foo  bar"),
+            Query::And(vec![word("foo"), word("bar")])
+        );
+        assert_eq!(
+            parse(r#""Foo bar" baz"#),
+            Query::And(vec![Query::Phrase("foo bar".to_owned()), word("baz")])
+        );
+        assert_eq!(
+            parse("foo -bar"),
+            Query::And(vec![word("foo"), Query::Not(Box::new(word("bar")))])
+        );
+        assert_eq!(
+            parse(r#"-"foo bar""#),
+            Query::And(vec![Query::Not(Box::new(Query::Phrase(
+                "foo bar".to_owned()
+            )))])
+        );
+        assert_eq!(
+            parse("a OR b OR c d"),
+            Query::And(vec![
+                Query::Or(vec![word("a"), word("b"), word("c")]),
+                word("d")
+            ])
+        );
+        // lowercase `or` is a word
+        assert_eq!(
+            parse("a or b"),
+            Query::And(vec![word("a"), word("or"), word("b")])
+        );
+        // dangling `OR` is ignored
+        assert_eq!(parse("OR a OR"), Query::And(vec![word("a")]));
+        assert_eq!(
+            parse(r#"title:"Foo Bar" links:20251224T000000Z linkedfrom:README created:2025-12"#),
+            Query::And(vec![
+                Query::Title("foo bar".to_owned()),
+                Query::Links(id("20251224T000000Z")?),
+                Query::LinkedFrom(id("README")?),
+                Query::Created("2025-12".to_owned()),
+            ])
+        );
+        assert_eq!(
+            parse(r#"prop:status prop:"status=In Progress""#),
+            Query::And(vec![
+                Query::Property("status".to_owned(), None),
+                Query::Property("status".to_owned(), Some("in progress".to_owned())),
+            ])
+        );
+        // invalid ID and unknown field are words
+        assert_eq!(
+            parse("links:foo https://example.com"),
+            Query::And(vec![word("links:foo"), word("https://example.com")])
+        );
+        assert_eq!(
+            parse("- title:"),
+            Query::And(vec![word("-"), word("title:")])
+        );
+        // unbalanced quote
+        assert_eq!(
+            parse(r#""foo bar"#),
+            Query::And(vec![Query::Phrase("foo bar".to_owned())])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_keywords() {
+        let config = crate::config::ConfigSearch::default();
+        assert_eq!(
+            Query::parse(r#"a "b c" -d e OR f title:g"#, &config).keywords(),
+            vec!["a", "b c", "e", "f"]
+        );
+    }
+
+    #[test]
+    fn test_matches() -> anyhow::Result<()> {
+        let config = crate::config::ConfigSearch::default();
+        let page_id = id("20251224T123456Z")?;
+        let page_meta = crate::page_meta::PageMeta {
+            links: [id("README")?].into_iter().collect(),
+            properties: crate::page_meta::PageProperties {
+                tags: vec!["Rust".to_owned(), "search".to_owned()],
+                ..Default::default()
+            },
+            tags: Default::default(),
+            title: Some("Sample Title".to_owned()),
+            title_links: Default::default(),
+        };
+        let backlinks = [id("20251225T000000Z")?].into_iter().collect();
+        let text = super::super::normalize("This is a sample page content.", &config);
+        let document = Document {
+            backlinks: Some(&backlinks),
+            page_id: &page_id,
+            page_meta: &page_meta,
+            text: &text,
+        };
+        let matches = |s: &str| Query::parse(s, &config).matches(&document, &config);
+
+        // single keyword
+        assert!(matches("This"));
+        assert!(matches("page"));
+        // ignore case
+        assert!(matches("this"));
+        // multiple keywords
+        assert!(matches("This page"));
+        assert!(matches("This\tpage"));
+        assert!(matches("This\u{3000}page"));
+        assert!(matches("This \t\u{3000}page"));
+        // not found
+        assert!(!matches("notfound"));
+        // phrase
+        assert!(matches(r#""sample page""#));
+        assert!(!matches(r#""page sample""#));
+        // exclusion
+        assert!(!matches("page -sample"));
+        assert!(matches("page -notfound"));
+        // or
+        assert!(matches("notfound OR page"));
+        assert!(!matches("notfound OR notfound2"));
+        // fields
+        assert!(matches("title:sample"));
+        assert!(!matches("title:content"));
+        assert!(matches("links:README"));
+        assert!(!matches("links:20251225T000000Z"));
+        assert!(matches("linkedfrom:20251225T000000Z"));
+        assert!(!matches("linkedfrom:README"));
+        assert!(matches("created:2025"));
+        assert!(matches("created:2025-12"));
+        assert!(matches("created:2025-12-24"));
+        assert!(!matches("created:2025-11"));
+        assert!(matches("prop:tags"));
+        assert!(matches("prop:tags=rust"));
+        assert!(matches("prop:tags=search"));
+        assert!(!matches("prop:tags=serde"));
+        assert!(!matches("prop:status"));
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+/// A part of a snippet. `matched` is `true` if the text matches a keyword.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub(crate) struct SnippetFragment {
+    pub(crate) matched: bool,
+    pub(crate) text: String,
+}
+
+/// The number of characters shown before the first match in a long line
+const CONTEXT_CHARS: usize = 40;
+/// The maximum number of characters in a snippet
+const MAX_CHARS: usize = 160;
+
+/// Extracts up to `max` lines of `content` that contain any of `keywords`.
+///
+/// `keywords` must be normalized. Lines are matched after normalization, but the snippets
+/// are taken from the raw `content`.
+pub(crate) fn snippets(
+    content: &str,
+    keywords: &[String],
+    config: &crate::config::ConfigSearch,
+    max: usize,
+) -> Vec<Vec<SnippetFragment>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| snippet(line, keywords, config))
+        .take(max)
+        .collect()
+}
+
+fn snippet(
+    line: &str,
+    keywords: &[String],
+    config: &crate::config::ConfigSearch,
+) -> Option<Vec<SnippetFragment>> {
+    // normalize each character (with its combining marks) separately to map positions back
+    let mut normalized = String::new();
+    // (start in normalized, start in line, end in line)
+    let mut groups = Vec::<(usize, usize, usize)>::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        while let Some((i, c)) =
+            chars.next_if(|(_, c)| matches!(c, '\u{3099}' | '\u{309A}' | '\u{FF9E}' | '\u{FF9F}'))
+        {
+            end = i + c.len_utf8();
+        }
+        groups.push((normalized.len(), start, end));
+        normalized.push_str(&super::normalize(&line[start..end], config));
+    }
+
+    let group_at = |pos: usize| groups[groups.partition_point(|(it, _, _)| *it <= pos) - 1];
+    let mut ranges = keywords
+        .iter()
+        .filter(|keyword| !keyword.is_empty())
+        .flat_map(|keyword| {
+            normalized
+                .match_indices(keyword.as_str())
+                .map(|(pos, _)| (group_at(pos).1, group_at(pos + keyword.len() - 1).2))
+                .collect::<Vec<(usize, usize)>>()
+        })
+        .collect::<Vec<(usize, usize)>>();
+    if ranges.is_empty() {
+        return None;
+    }
+    ranges.sort();
+    let mut merged = Vec::<(usize, usize)>::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    // clip long lines around the first match
+    let offsets = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(line.len()))
+        .collect::<Vec<usize>>();
+    let first = offsets.partition_point(|it| *it < merged[0].0);
+    let window_start = offsets[first.saturating_sub(CONTEXT_CHARS)];
+    let window_end = offsets
+        [(offsets.binary_search(&window_start).unwrap_or(0) + MAX_CHARS).min(offsets.len() - 1)];
+
+    let mut fragments = vec![];
+    let mut push = |matched: bool, text: &str| {
+        if !text.is_empty() {
+            fragments.push(SnippetFragment {
+                matched,
+                text: text.to_owned(),
+            });
+        }
+    };
+    if window_start > 0 {
+        push(false, "…");
+    }
+    let mut pos = window_start;
+    for (start, end) in merged {
+        let (start, end) = (start.clamp(pos, window_end), end.clamp(pos, window_end));
+        push(false, &line[pos..start]);
+        push(true, &line[start..end]);
+        pos = end;
+    }
+    push(false, &line[pos..window_end]);
+    if window_end < line.len() {
+        push(false, "…");
+    }
+    Some(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(snippet: &[SnippetFragment]) -> String {
+        snippet
+            .iter()
+            .map(|it| {
+                if it.matched {
+                    format!("[{}]", it.text)
+                } else {
+                    it.text.clone()
+                }
+            })
+            .collect::<String>()
+    }
+
+    #[test]
+    fn test_snippets() {
+        let config = crate::config::ConfigSearch::default();
+        let keywords = |s: &[&str]| s.iter().map(|it| it.to_string()).collect::<Vec<String>>();
+
+        let content = "# Rust\n\nNo match.\n  Rust and RUST.  \nrust again.\n";
+        assert_eq!(
+            snippets(content, &keywords(&["rust"]), &config, 2)
+                .iter()
+                .map(|it| render(it))
+                .collect::<Vec<String>>(),
+            vec!["# [Rust]", "[Rust] and [RUST]."]
+        );
+
+        // overlapping keywords are merged
+        assert_eq!(
+            snippets("abcdef", &keywords(&["bcd", "cde"]), &config, 1)
+                .iter()
+                .map(|it| render(it))
+                .collect::<Vec<String>>(),
+            vec!["a[bcde]f"]
+        );
+
+        // normalized match is mapped back to the raw text
+        assert_eq!(
+            snippets(
+                "全角のＲｕｓｔとｶﾞｲﾄﾞ",
+                &keywords(&["rust", "ガイド"]),
+                &config,
+                1
+            )
+            .iter()
+            .map(|it| render(it))
+            .collect::<Vec<String>>(),
+            vec!["全角の[Ｒｕｓｔ]と[ｶﾞｲﾄﾞ]"]
+        );
+
+        // long lines are clipped around the first match
+        let line = format!("{}match{}", "a".repeat(100), "b".repeat(200));
+        let snippet = &snippets(&line, &keywords(&["match"]), &config, 1)[0];
+        assert_eq!(
+            render(snippet),
+            format!("…{}[match]{}…", "a".repeat(40), "b".repeat(115))
+        );
+
+        assert!(snippets(content, &keywords(&["notfound"]), &config, 2).is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 mod edit;
 mod get;
 mod image;
+mod list;
 mod new;
+mod search;
 mod serve;
+mod tags;
 
 #[derive(clap::Subcommand)]
 pub(crate) enum Subcommand {
@@ -13,10 +16,16 @@ pub(crate) enum Subcommand {
     /// Manage images
     #[command(subcommand)]
     Image(self::image::Subcommand),
+    /// List pages
+    List(self::list::Args),
     /// Create a new page
     New,
+    /// Search pages
+    Search(self::search::Args),
     /// Start the local server
     Serve,
+    /// List tags, or the pages with the tag
+    Tags(self::tags::Args),
 }
 
 impl Subcommand {
@@ -25,8 +34,11 @@ impl Subcommand {
             Subcommand::Edit(args) => self::edit::execute(args).await,
             Subcommand::Get(args) => self::get::execute(args).await,
             Subcommand::Image(subcommand) => self::image::execute(subcommand).await,
+            Subcommand::List(args) => self::list::execute(args).await,
             Subcommand::New => self::new::execute().await,
+            Subcommand::Search(args) => self::search::execute(args).await,
             Subcommand::Serve => self::serve::execute().await,
+            Subcommand::Tags(args) => self::tags::execute(args).await,
         }
     }
 }
@@ -0,0 +1,156 @@
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// The output format
+    #[arg(default_value = "table", long, value_enum)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum Format {
+    Json,
+    Table,
+    Tsv,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Row {
+    id: String,
+    title: String,
+    links: usize,
+    backlinks: usize,
+}
+
+pub(super) async fn execute(Args { format }: Args) -> anyhow::Result<()> {
+    let config = crate::config::Config::load().await?;
+    let rows = read_rows(&config)?;
+    let mut stdout = std::io::stdout().lock();
+    write_rows(&mut stdout, format, &rows)?;
+    Ok(())
+}
+
+fn read_rows(config: &crate::config::Config) -> anyhow::Result<Vec<Row>> {
+    let index = crate::index::Index::new_without_terms(config.clone())?;
+    Ok(index
+        .page_metas
+        .iter()
+        .map(|(page_id, page_meta)| Row {
+            id: page_id.to_string(),
+            title: page_meta.title.clone().unwrap_or_default(),
+            links: index.links(page_id).len(),
+            backlinks: index
+                .backlinks
+                .get(page_id)
+                .map(|set| set.len())
+                .unwrap_or(0),
+        })
+        .collect::<Vec<Row>>())
+}
+
+fn write_rows<W: std::io::Write>(w: &mut W, format: Format, rows: &[Row]) -> anyhow::Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer(&mut *w, rows)?;
+            writeln!(w)?;
+        }
+        Format::Table => {
+            // the title is placed last because its display width is not predictable
+            writeln!(w, "{:<16}  {:>5}  {:>9}  TITLE", "ID", "LINKS", "BACKLINKS")?;
+            for Row {
+                id,
+                title,
+                links,
+                backlinks,
+            } in rows
+            {
+                writeln!(w, "{id:<16}  {links:>5}  {backlinks:>9}  {title}")?;
+            }
+        }
+        Format::Tsv => {
+            for Row {
+                id,
+                title,
+                links,
+                backlinks,
+            } in rows
+            {
+                writeln!(
+                    w,
+                    "{id}\t{}\t{links}\t{backlinks}",
+                    title.replace('\t', " ")
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_rows() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir();
+        std::fs::write(data_dir.join("20251224T000000Z.md"), "# Page 1\n")?;
+        std::fs::write(
+            data_dir.join("20251224T000001Z.md"),
+            "# Page 2\n\n[20251224T000000Z] [[Page 1]] [[Page 3]]\n",
+        )?;
+
+        assert_eq!(
+            read_rows(&config)?,
+            vec![
+                Row {
+                    id: "20251224T000000Z".to_owned(),
+                    title: "Page 1".to_owned(),
+                    links: 0,
+                    backlinks: 1,
+                },
+                Row {
+                    id: "20251224T000001Z".to_owned(),
+                    title: "Page 2".to_owned(),
+                    // the ID and the title link to the same page, and `Page 3` does not exist
+                    links: 1,
+                    backlinks: 0,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_rows() -> anyhow::Result<()> {
+        let rows = vec![Row {
+            id: "20251224T000000Z".to_owned(),
+            title: "Title\twith tab".to_owned(),
+            links: 2,
+            backlinks: 3,
+        }];
+
+        let mut buf = vec![];
+        write_rows(&mut buf, Format::Json, &rows)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            "[{\"id\":\"20251224T000000Z\",\"title\":\"Title\\twith tab\",\"links\":2,\"backlinks\":3}]\n"
+        );
+
+        let mut buf = vec![];
+        write_rows(&mut buf, Format::Table, &rows)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            concat!(
+                "ID                LINKS  BACKLINKS  TITLE\n",
+                "20251224T000000Z      2          3  Title\twith tab\n",
+            )
+        );
+
+        let mut buf = vec![];
+        write_rows(&mut buf, Format::Tsv, &rows)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            "20251224T000000Z\tTitle with tab\t2\t3\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// Output the result as JSON
+    #[arg(long)]
+    json: bool,
+    /// Search only page titles and aliases
+    #[arg(long)]
+    title_only: bool,
+    /// The query (e.g. `foo "bar baz" -qux title:foo links:README created:2025-12`)
+    #[arg(required = true)]
+    query: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Hit {
+    id: String,
+    title: String,
+    context: Option<String>,
+}
+
+pub(super) async fn execute(
+    Args {
+        json,
+        title_only,
+        query,
+    }: Args,
+) -> anyhow::Result<()> {
+    let config = crate::config::Config::load().await?;
+    let q = query.join(" ");
+    let hits = search(&config, &q, title_only)?;
+    if json {
+        println!("{}", serde_json::to_string(&hits)?);
+    } else {
+        for Hit { id, title, context } in hits {
+            println!("{id} {title}");
+            if let Some(context) = context {
+                println!("    {context}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn search(config: &crate::config::Config, q: &str, title_only: bool) -> anyhow::Result<Vec<Hit>> {
+    let search_config = config.search();
+    let index = crate::index::Index::new(config.clone())?;
+
+    let mut hits = vec![];
+    if title_only {
+        let query = crate::search::Query::parse(q, &search_config);
+        for (page_id, page_meta) in &index.page_metas {
+            let title = page_meta.title.clone().unwrap_or_default();
+            // the aliases are titles too (one per line so that a phrase does not span them)
+            let text = page_meta
+                .titles()
+                .iter()
+                .map(|it| crate::search::normalize(it, &search_config))
+                .collect::<Vec<String>>()
+                .join("\n");
+            let document = crate::search::Document {
+                backlinks: index.backlinks.get(page_id).map(|it| &**it),
+                page_id,
+                page_meta,
+                text: &text,
+            };
+            if query.matches(&document, &search_config) {
+                hits.push(Hit {
+                    id: page_id.to_string(),
+                    title,
+                    context: None,
+                });
+            }
+        }
+    } else {
+        let keywords = crate::search::Query::parse(q, &search_config).keywords();
+        for (page_id, _score) in index.search(q) {
+            let content = crate::page_io::PageIo::read_page_raw_content(config, &page_id)?;
+            hits.push(Hit {
+                id: page_id.to_string(),
+                title: index
+                    .page_metas
+                    .get(&page_id)
+                    .and_then(|it| it.title.clone())
+                    .unwrap_or_default(),
+                context: crate::search::find_context_line(
+                    crate::page_meta::PageProperties::split_front_matter(&content).1,
+                    &keywords,
+                    &search_config,
+                )
+                .map(str::to_owned),
+            });
+        }
+    }
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir();
+        std::fs::write(
+            data_dir.join("20251224T000000Z.md"),
+            "# Rust\n\nAbout ownership.\n",
+        )?;
+        std::fs::write(
+            data_dir.join("20251224T000001Z.md"),
+            "# Ownership\n\nSee [20251224T000000Z].\n",
+        )?;
+        std::fs::write(
+            data_dir.join("20251224T000002Z.md"),
+            "---\naliases: [Borrowing]\n---\n# C\n\nNo borrowing here.\n",
+        )?;
+
+        assert_eq!(
+            search(&config, "ownership", false)?,
+            vec![
+                Hit {
+                    id: "20251224T000001Z".to_owned(),
+                    title: "Ownership".to_owned(),
+                    context: Some("# Ownership".to_owned()),
+                },
+                Hit {
+                    id: "20251224T000000Z".to_owned(),
+                    title: "Rust".to_owned(),
+                    context: Some("About ownership.".to_owned()),
+                },
+            ]
+        );
+        assert_eq!(
+            search(&config, "ownership", true)?,
+            vec![Hit {
+                id: "20251224T000001Z".to_owned(),
+                title: "Ownership".to_owned(),
+                context: None,
+            }]
+        );
+        // the context line is not taken from the front matter
+        assert_eq!(
+            search(&config, "borrowing", false)?,
+            vec![Hit {
+                id: "20251224T000002Z".to_owned(),
+                title: "C".to_owned(),
+                context: Some("No borrowing here.".to_owned()),
+            }]
+        );
+        // aliases are titles
+        for (q, title_only) in [("borrowing", true), ("title:borrowing", false)] {
+            assert_eq!(
+                search(&config, q, title_only)?,
+                vec![Hit {
+                    id: "20251224T000002Z".to_owned(),
+                    title: "C".to_owned(),
+                    context: None,
+                }]
+            );
+        }
+        assert_eq!(
+            search(&config, "ownership -links:20251224T000000Z", false)?,
+            vec![Hit {
+                id: "20251224T000000Z".to_owned(),
+                title: "Rust".to_owned(),
+                context: Some("About ownership.".to_owned()),
+            }]
+        );
+        Ok(())
+    }
+}
@@ -1,85 +1,247 @@
+mod auth;
+mod etag;
 mod handler;
-mod index;
-
-use anyhow::Context as _;
+mod html_cache;
+mod watcher;
 
+/// The state shared by the handlers and the watcher.
+///
+/// Readers take a snapshot of the index with [`State::index`] and never block each other.
+/// Writers build a new index and swap it in.
 struct State {
     config: crate::config::Config,
-    index: self::index::Index,
+    events: tokio::sync::broadcast::Sender<PageChanged>,
+    html_cache: self::html_cache::HtmlCache,
+    index: std::sync::RwLock<std::sync::Arc<crate::index::Index>>,
+    /// Serializes writes to the pages and the index
+    update_lock: std::sync::Mutex<()>,
 }
 
-pub(super) async fn execute() -> anyhow::Result<()> {
-    let config = crate::config::Config::load().await?;
+/// The event sent to `/events` when a page is created, updated or removed.
+#[derive(Clone, Debug, serde::Serialize)]
+struct PageChanged {
+    /// `true` if any page may have changed (after a full rescan). `id` and `links` are empty.
+    all: bool,
+    id: String,
+    /// The pages linked from the page before or after the change (their backlinks may change)
+    links: Vec<String>,
+}
 
-    // create index
-    let index = self::index::Index::new(config.clone())?;
+impl State {
+    fn new(config: crate::config::Config, index: crate::index::Index) -> Self {
+        let (events, _) = tokio::sync::broadcast::channel(16);
+        Self {
+            config,
+            events,
+            html_cache: Default::default(),
+            index: std::sync::RwLock::new(std::sync::Arc::new(index)),
+            update_lock: std::sync::Mutex::new(()),
+        }
+    }
 
-    let port = config.port();
+    /// Returns a snapshot of the index.
+    fn index(&self) -> anyhow::Result<std::sync::Arc<crate::index::Index>> {
+        let index = self
+            .index
+            .read()
+            .map_err(|_| anyhow::anyhow!("index lock poisoned"))?;
+        Ok(index.clone())
+    }
 
-    let watch_dir = config.data_dir().to_path_buf();
-    let state = std::sync::Arc::new(std::sync::Mutex::new(State { config, index }));
+    /// Runs `write` and reloads the page. Writes by other requests or the watcher are not
+    /// interleaved.
+    fn modify_page<T, F>(&self, page_id: &crate::page_id::PageId, write: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&crate::config::Config) -> anyhow::Result<T>,
+    {
+        let _guard = self
+            .update_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("update lock poisoned"))?;
+        let result = write(&self.config)?;
+        self.reload_pages_locked(&std::collections::BTreeSet::from([page_id.clone()]))?;
+        Ok(result)
+    }
 
-    // run watcher
-    fn update_page_meta(
-        state: std::sync::Arc<std::sync::Mutex<State>>,
-        path: &std::path::Path,
+    /// Reloads the pages from the disk into the index and invalidates the rendered HTML.
+    ///
+    /// The index is copied and swapped once for all the pages. If some pages fail to reload, the
+    /// others are still reloaded.
+    fn reload_pages(
+        &self,
+        page_ids: &std::collections::BTreeSet<crate::page_id::PageId>,
     ) -> anyhow::Result<()> {
-        let mut state = state.lock().map_err(|_| anyhow::anyhow!("locking state"))?;
+        let _guard = self
+            .update_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("update lock poisoned"))?;
+        self.reload_pages_locked(page_ids)
+    }
+
+    /// Replaces the whole index (e.g. after a full rescan).
+    fn replace_index(&self, index: crate::index::Index) -> anyhow::Result<()> {
+        let _guard = self
+            .update_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("update lock poisoned"))?;
+        self.swap_index(index)?;
+        self.html_cache.clear();
+        // ignore the error when no one is listening
+        let _ = self.events.send(PageChanged {
+            all: true,
+            id: String::new(),
+            links: vec![],
+        });
+        Ok(())
+    }
 
-        let page_id = crate::page_io::PageIo::page_id(path)?;
+    fn reload_pages_locked(
+        &self,
+        page_ids: &std::collections::BTreeSet<crate::page_id::PageId>,
+    ) -> anyhow::Result<()> {
+        let old_index = self.index()?;
 
-        if !path.exists() {
-            state.index.remove(&page_id);
-            return Ok(());
+        // readers keep using the old snapshot while the new one is built
+        let mut index = crate::index::Index::clone(&old_index);
+        let mut changes = vec![];
+        let mut errors = vec![];
+        for page_id in page_ids {
+            let result = if crate::page_io::PageIo::page_path(&self.config, page_id).exists() {
+                index.update(page_id)
+            } else {
+                index.remove(page_id);
+                Ok(())
+            };
+            match result {
+                Ok(()) => changes.push(page_id),
+                Err(e) => errors.push(format!("{page_id}: {e:#}")),
+            }
         }
 
-        state.index.update(&page_id)?;
+        let titles = |index: &crate::index::Index, page_id: &crate::page_id::PageId| {
+            index
+                .page_metas
+                .get(page_id)
+                .map(|it| it.titles())
+                .unwrap_or_default()
+        };
+        let titles_changed = changes
+            .iter()
+            .any(|page_id| titles(&old_index, page_id) != titles(&index, page_id));
+        let events = changes
+            .iter()
+            .map(|page_id| PageChanged {
+                all: false,
+                id: page_id.to_string(),
+                links: old_index
+                    .links(page_id)
+                    .union(&index.links(page_id))
+                    .map(|it| it.to_string())
+                    .collect::<Vec<String>>(),
+            })
+            .collect::<Vec<PageChanged>>();
+        drop(old_index);
+        self.swap_index(index)?;
 
+        if titles_changed {
+            // `[[Title]]` links in other pages may be resolved differently
+            self.html_cache.clear();
+        } else {
+            for page_id in &changes {
+                self.html_cache.remove(page_id);
+            }
+        }
+
+        for event in events {
+            // ignore the error when no one is listening
+            let _ = self.events.send(event);
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("reload {}", errors.join(", "));
+        }
         Ok(())
     }
 
-    async fn new_watcher(
-        state_for_watcher: std::sync::Arc<std::sync::Mutex<State>>,
-        watch_dir: std::path::PathBuf,
-    ) -> anyhow::Result<()> {
-        let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
-        let mut watcher = notify::recommended_watcher(tx).context("create watcher")?;
-        notify::Watcher::watch(&mut watcher, &watch_dir, notify::RecursiveMode::Recursive)
-            .context("watch dir")?;
-        for res in rx {
-            match res {
-                Ok(event) => {
-                    match event.kind {
-                        notify::EventKind::Any
-                        | notify::EventKind::Access(_)
-                        | notify::EventKind::Other => {
-                            // do nothing
-                        }
-                        notify::EventKind::Create(_)
-                        | notify::EventKind::Modify(_)
-                        | notify::EventKind::Remove(_) => {
-                            for path in event.paths {
-                                update_page_meta(state_for_watcher.clone(), &path)
-                                    .context("update page meta")?;
-                            }
-                        }
-                    }
+    fn swap_index(&self, index: crate::index::Index) -> anyhow::Result<()> {
+        *self
+            .index
+            .write()
+            .map_err(|_| anyhow::anyhow!("index lock poisoned"))? = std::sync::Arc::new(index);
+        Ok(())
+    }
+}
+
+pub(super) async fn execute() -> anyhow::Result<()> {
+    let config = crate::config::Config::load().await?;
+
+    // create index
+    let index = crate::index::Index::new(config.clone())?;
+
+    let bind_address = config.bind_address();
+    let port = config.port();
+
+    let state = std::sync::Arc::new(State::new(config, index));
+
+    self::watcher::spawn(state.clone());
+
+    let router = router(state);
+
+    match bind_address {
+        crate::config::ConfigBindAddress::Ip(ip_addr) => {
+            let listener = tokio::net::TcpListener::bind((ip_addr, port)).await?;
+            axum::serve(listener, router).await?;
+        }
+        #[cfg(unix)]
+        crate::config::ConfigBindAddress::Unix(path) => {
+            if std::fs::symlink_metadata(&path)
+                .is_ok_and(|it| std::os::unix::fs::FileTypeExt::is_socket(&it.file_type()))
+            {
+                if tokio::net::UnixStream::connect(&path).await.is_ok() {
+                    anyhow::bail!("{} is in use by another server", path.display());
                 }
-                Err(e) => anyhow::bail!("watch error: {:?}", e),
+                // remove the socket left by the previous run
+                std::fs::remove_file(&path)?;
             }
+            let listener = tokio::net::UnixListener::bind(&path)?;
+            axum::serve(listener, router).await?;
         }
-        Ok(())
     }
-    tokio::spawn(new_watcher(state.clone(), watch_dir));
+    Ok(())
+}
 
-    let router = axum::Router::new()
+fn router(state: std::sync::Arc<State>) -> axum::Router {
+    axum::Router::new()
         .route(
             "/",
             axum::routing::get(self::handler::get_root_or_list_pages),
         )
         .route("/{id}", axum::routing::get(self::handler::get))
-        .route("/pages", axum::routing::get(self::handler::list))
-        .route("/pages/{id}", axum::routing::get(self::handler::get))
+        .route(
+            "/api/pages",
+            axum::routing::get(self::handler::api_list).post(self::handler::api_create),
+        )
+        .route(
+            "/api/pages/{id}",
+            axum::routing::get(self::handler::api_get).put(self::handler::api_update),
+        )
+        .route("/api/search", axum::routing::get(self::handler::api_search))
+        .route(
+            "/api/titles",
+            axum::routing::get(self::handler::api_list_titles),
+        )
+        .route("/events", axum::routing::get(self::handler::events))
+        .route(
+            "/pages",
+            axum::routing::get(self::handler::list).post(self::handler::create),
+        )
+        .route(
+            "/pages/{id}",
+            axum::routing::get(self::handler::get)
+                .post(self::handler::update)
+                .put(self::handler::update),
+        )
+        .route("/pages/{id}/edit", axum::routing::get(self::handler::edit))
         .route(
             "/images/{image_name}",
             axum::routing::get(self::handler::get_image),
@@ -92,16 +254,100 @@ pub(super) async fn execute() -> anyhow::Result<()> {
             "/styles/index.css",
             axum::routing::get(self::handler::get_style_index),
         )
+        .route("/tags", axum::routing::get(self::handler::list_tags))
+        .route("/tags/{tag}", axum::routing::get(self::handler::get_tag))
         .route("/titles", axum::routing::get(self::handler::list_titles))
         .route(
             "/titles/{title}",
             axum::routing::get(self::handler::get_page_by_title),
         )
-        .with_state(state);
+        .with_state(state.clone())
+        .layer(axum::middleware::from_fn(self::etag::middleware))
+        .layer(
+            tower_http::compression::CompressionLayer::new().compress_when(
+                tower_http::compression::predicate::Predicate::and(
+                    tower_http::compression::predicate::DefaultPredicate::new(),
+                    compressible,
+                ),
+            ),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state,
+            self::auth::middleware,
+        ))
+}
 
-    let ip_addr = <std::net::IpAddr as std::str::FromStr>::from_str("127.0.0.1")
-        .expect("127.0.0.1 to be valid as IpAddr");
-    let listener = tokio::net::TcpListener::bind((ip_addr, port)).await?;
-    axum::serve(listener, router).await?;
-    Ok(())
+/// Returns `true` if the response is a page, the script or the style.
+///
+/// Images and other media are not compressed because the compression removes `Accept-Ranges`
+/// and `Content-Length`, which players need to seek.
+fn compressible(
+    _status: axum::http::StatusCode,
+    _version: axum::http::Version,
+    headers: &axum::http::HeaderMap,
+    _extensions: &axum::http::Extensions,
+) -> bool {
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|it| it.to_str().ok())
+        .unwrap_or_default();
+    self::etag::CONTENT_TYPES
+        .iter()
+        .any(|it| content_type.starts_with(it))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_index_sends_event() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let index = crate::index::Index::new(config.clone())?;
+        let state = State::new(config.clone(), index);
+        let mut receiver = state.events.subscribe();
+
+        state.replace_index(crate::index::Index::new(config)?)?;
+        let page_changed = receiver.try_recv()?;
+        assert!(page_changed.all);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_router_compression() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        std::fs::create_dir_all(config.images_dir())?;
+        std::fs::write(config.images_dir().join("x.mp4"), vec![0_u8; 1024])?;
+        let index = crate::index::Index::new(config.clone())?;
+        let router = router(std::sync::Arc::new(State::new(config, index)));
+        let get = |uri: &str| {
+            tower::ServiceExt::oneshot(
+                router.clone(),
+                axum::http::Request::get(uri)
+                    .header(axum::http::header::ACCEPT_ENCODING, "gzip")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = get("/pages").await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_ENCODING),
+            Some(&axum::http::HeaderValue::from_static("gzip"))
+        );
+
+        // media stays seekable
+        let response = get("/images/x.mp4").await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get(axum::http::header::CONTENT_ENCODING),
+            None
+        );
+        assert_eq!(
+            response.headers().get(axum::http::header::ACCEPT_RANGES),
+            Some(&axum::http::HeaderValue::from_static("bytes"))
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,84 @@
+/// Rejects requests without the configured access token with `401 Unauthorized`.
+///
+/// The token is accepted as a bearer token (`Authorization: Bearer <token>`) or as the
+/// password of basic auth (the user name is ignored), so both scripts and browsers can use it.
+pub(super) async fn middleware(
+    axum::extract::State(state): axum::extract::State<std::sync::Arc<super::State>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let Some(access_token) = state.config.access_token() else {
+        return next.run(request).await;
+    };
+    let authorization = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|it| it.to_str().ok());
+    if authorization.is_some_and(|it| authorized(it, access_token)) {
+        return next.run(request).await;
+    }
+    axum::response::IntoResponse::into_response((
+        axum::http::StatusCode::UNAUTHORIZED,
+        [(
+            axum::http::header::WWW_AUTHENTICATE,
+            r#"Basic realm="fubako", charset="UTF-8""#,
+        )],
+    ))
+}
+
+/// Returns `true` if the `Authorization` header value carries `access_token`.
+fn authorized(authorization: &str, access_token: &str) -> bool {
+    let Some((scheme, credentials)) = authorization.trim().split_once(' ') else {
+        return false;
+    };
+    let credentials = credentials.trim();
+    if scheme.eq_ignore_ascii_case("bearer") {
+        constant_time_eq(credentials.as_bytes(), access_token.as_bytes())
+    } else if scheme.eq_ignore_ascii_case("basic") {
+        let Ok(decoded) =
+            base64::Engine::decode(&base64::engine::general_purpose::STANDARD, credentials)
+        else {
+            return false;
+        };
+        let Some(separator) = decoded.iter().position(|it| *it == b':') else {
+            return false;
+        };
+        constant_time_eq(&decoded[separator + 1..], access_token.as_bytes())
+    } else {
+        false
+    }
+}
+
+/// Compares `a` and `b` in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0_u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorized() {
+        assert!(authorized("Bearer secret", "secret"));
+        assert!(authorized("bearer  secret ", "secret"));
+        assert!(!authorized("Bearer secret2", "secret"));
+        assert!(!authorized("Bearer", "secret"));
+
+        // "user:secret"
+        assert!(authorized("Basic dXNlcjpzZWNyZXQ=", "secret"));
+        // ":secret"
+        assert!(authorized("Basic OnNlY3JldA==", "secret"));
+        // "secret:user"
+        assert!(!authorized("Basic c2VjcmV0OnVzZXI=", "secret"));
+        // "secret"
+        assert!(!authorized("Basic c2VjcmV0", "secret"));
+        assert!(!authorized("Basic !!!", "secret"));
+
+        assert!(!authorized("Digest secret", "secret"));
+        assert!(!authorized("", "secret"));
+    }
+}
@@ -0,0 +1,97 @@
+/// The content types of the responses that get an `ETag` (and are compressed)
+pub(super) const CONTENT_TYPES: &[&str] = &["application/javascript", "text/css", "text/html"];
+
+/// Adds a weak `ETag` derived from the body to `GET` responses and answers `304 Not Modified`
+/// if it matches `If-None-Match`.
+///
+/// The body reflects both the page content and the index state (titles, backlinks, ...), so
+/// the `ETag` changes whenever either of them changes.
+pub(super) async fn middleware(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let is_get = matches!(
+        *request.method(),
+        axum::http::Method::GET | axum::http::Method::HEAD
+    );
+    let if_none_match = request
+        .headers()
+        .get(axum::http::header::IF_NONE_MATCH)
+        .and_then(|it| it.to_str().ok())
+        .map(str::to_owned);
+
+    let response = next.run(request).await;
+    let content_type = response
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|it| it.to_str().ok())
+        .unwrap_or_default();
+    if !is_get
+        || response.status() != axum::http::StatusCode::OK
+        || !CONTENT_TYPES.iter().any(|it| content_type.starts_with(it))
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return axum::response::IntoResponse::into_response(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            );
+        }
+    };
+    let etag = etag(&bytes);
+    // `etag` consists of ASCII characters only
+    if let Ok(value) = axum::http::HeaderValue::from_str(&etag) {
+        parts.headers.insert(axum::http::header::ETAG, value);
+    }
+    // revalidate instead of using a stale page
+    parts.headers.insert(
+        axum::http::header::CACHE_CONTROL,
+        axum::http::HeaderValue::from_static("no-cache"),
+    );
+    if if_none_match.is_some_and(|it| matches_etag(&it, &etag)) {
+        parts.status = axum::http::StatusCode::NOT_MODIFIED;
+        parts.headers.remove(axum::http::header::CONTENT_LENGTH);
+        return axum::response::Response::from_parts(parts, axum::body::Body::empty());
+    }
+    axum::response::Response::from_parts(parts, axum::body::Body::from(bytes))
+}
+
+/// Returns a weak `ETag` for the body. It is weak because the body may be compressed.
+fn etag(body: &[u8]) -> String {
+    let mut hasher = std::hash::DefaultHasher::new();
+    std::hash::Hasher::write(&mut hasher, body);
+    format!("W/\"{:016x}\"", std::hash::Hasher::finish(&hasher))
+}
+
+/// Returns `true` if `if_none_match` contains `etag` (weak comparison).
+pub(super) fn matches_etag(if_none_match: &str, etag: &str) -> bool {
+    let opaque_tag = |s: &str| s.trim().trim_start_matches("W/").to_owned();
+    if_none_match
+        .split(',')
+        .any(|it| it.trim() == "*" || opaque_tag(it) == opaque_tag(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag() {
+        assert_eq!(etag(b"foo"), etag(b"foo"));
+        assert_ne!(etag(b"foo"), etag(b"bar"));
+        assert!(etag(b"foo").starts_with("W/\""));
+    }
+
+    #[test]
+    fn test_matches_etag() {
+        assert!(matches_etag(r#"W/"abc""#, r#"W/"abc""#));
+        assert!(matches_etag(r#""abc""#, r#"W/"abc""#));
+        assert!(matches_etag(r#""xyz", W/"abc""#, r#"W/"abc""#));
+        assert!(matches_etag("*", r#"W/"abc""#));
+        assert!(!matches_etag(r#"W/"xyz""#, r#"W/"abc""#));
+    }
+}
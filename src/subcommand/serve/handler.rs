@@ -1,17 +1,136 @@
+mod api_create;
+mod api_get;
+mod api_list;
+mod api_list_titles;
+mod api_search;
+mod api_update;
+mod create;
+mod edit;
+mod events;
 mod get;
 mod get_image;
 mod get_page_by_title;
 mod get_root_or_list_pages;
 mod get_script_index;
 mod get_style_index;
+mod get_tag;
 mod list;
+mod list_tags;
 mod list_titles;
+mod update;
 
+pub use self::api_create::handle as api_create;
+pub use self::api_get::handle as api_get;
+pub use self::api_list::handle as api_list;
+pub use self::api_list_titles::handle as api_list_titles;
+pub use self::api_search::handle as api_search;
+pub use self::api_update::handle as api_update;
+pub use self::create::handle as create;
+pub use self::edit::handle as edit;
+pub use self::events::handle as events;
 pub use self::get::handle as get;
 pub use self::get_image::handle as get_image;
 pub use self::get_page_by_title::handle as get_page_by_title;
 pub use self::get_root_or_list_pages::handle as get_root_or_list_pages;
 pub use self::get_script_index::handle as get_script_index;
 pub use self::get_style_index::handle as get_style_index;
+pub use self::get_tag::handle as get_tag;
 pub use self::list::handle as list;
+pub use self::list_tags::handle as list_tags;
 pub use self::list_titles::handle as list_titles;
+pub use self::update::handle as update;
+
+/// Runs `f` on the thread pool for blocking operations such as file IO and rendering.
+async fn spawn_blocking<T, F>(f: F) -> Result<T, axum::http::StatusCode>
+where
+    F: FnOnce() -> Result<T, axum::http::StatusCode> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+}
+
+/// Rejects form submissions from other sites with `403 Forbidden`.
+///
+/// Browsers send the basic auth credentials with cross-site form posts too, so a page on
+/// another site could otherwise modify pages. Requests without `Sec-Fetch-Site` and `Origin`
+/// (e.g. from scripts) are allowed.
+fn check_same_origin(headers: &axum::http::HeaderMap) -> Result<(), axum::http::StatusCode> {
+    let header = |name: axum::http::HeaderName| {
+        headers
+            .get(name)
+            .map(|it| it.to_str().map_err(|_| axum::http::StatusCode::FORBIDDEN))
+            .transpose()
+    };
+    let same_origin = if let Some(sec_fetch_site) =
+        header(axum::http::HeaderName::from_static("sec-fetch-site"))?
+    {
+        matches!(sec_fetch_site, "same-origin" | "none")
+    } else if let Some(origin) = header(axum::http::header::ORIGIN)? {
+        let host = header(axum::http::header::HOST)?;
+        origin
+            .split_once("://")
+            .is_some_and(|(_, authority)| Some(authority) == host)
+    } else {
+        true
+    };
+    if same_origin {
+        Ok(())
+    } else {
+        Err(axum::http::StatusCode::FORBIDDEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_same_origin() {
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        axum::http::HeaderName::from_static(name),
+                        axum::http::HeaderValue::from_static(value),
+                    )
+                })
+                .collect::<axum::http::HeaderMap>()
+        };
+        let forbidden = Err(axum::http::StatusCode::FORBIDDEN);
+
+        assert_eq!(check_same_origin(&headers(&[])), Ok(()));
+        assert_eq!(
+            check_same_origin(&headers(&[("sec-fetch-site", "same-origin")])),
+            Ok(())
+        );
+        assert_eq!(
+            check_same_origin(&headers(&[("sec-fetch-site", "cross-site")])),
+            forbidden
+        );
+        assert_eq!(
+            check_same_origin(&headers(&[("sec-fetch-site", "same-site")])),
+            forbidden
+        );
+        assert_eq!(
+            check_same_origin(&headers(&[
+                ("origin", "http://192.168.0.2:3000"),
+                ("host", "192.168.0.2:3000"),
+            ])),
+            Ok(())
+        );
+        assert_eq!(
+            check_same_origin(&headers(&[
+                ("origin", "https://example.com"),
+                ("host", "192.168.0.2:3000"),
+            ])),
+            forbidden
+        );
+        assert_eq!(
+            check_same_origin(&headers(&[("origin", "null"), ("host", "localhost")])),
+            forbidden
+        );
+    }
+}
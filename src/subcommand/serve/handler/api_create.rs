@@ -0,0 +1,47 @@
+#[derive(serde::Deserialize)]
+pub struct ApiCreateRequestBody {
+    /// The content of the new page. If omitted, it is made from `title` and `from`.
+    content: Option<String>,
+    /// The page ID to link back to from the new page
+    from: Option<crate::page_id::PageId>,
+    /// The title of the new page
+    title: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ApiCreateResponseBody {
+    id: String,
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::Json(ApiCreateRequestBody {
+        content,
+        from,
+        title,
+    }): axum::Json<ApiCreateRequestBody>,
+) -> Result<
+    (
+        axum::http::StatusCode,
+        [(axum::http::HeaderName, String); 1],
+        axum::Json<ApiCreateResponseBody>,
+    ),
+    axum::http::StatusCode,
+> {
+    let content =
+        content.unwrap_or_else(|| super::create::new_page_content(title.as_deref(), from.as_ref()));
+    let page_id =
+        super::spawn_blocking(move || super::create::create_page(&state, &content)).await?;
+    Ok((
+        axum::http::StatusCode::CREATED,
+        [(
+            axum::http::header::LOCATION,
+            format!("/api/pages/{}", page_id),
+        )],
+        axum::Json(ApiCreateResponseBody {
+            id: page_id.to_string(),
+        }),
+    ))
+}
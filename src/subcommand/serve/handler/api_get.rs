@@ -0,0 +1,124 @@
+#[derive(serde::Serialize)]
+pub struct ApiGetResponse {
+    pub(crate) backlinks: Vec<String>,
+    pub(crate) content: String,
+    pub(crate) html: String,
+    pub(crate) id: String,
+    pub(crate) links: Vec<String>,
+    /// The modification time to pass to `PUT /api/pages/{id}`
+    pub(crate) modified: String,
+    pub(crate) properties: crate::page_meta::PageProperties,
+    pub(crate) tags: Vec<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) title_links: Vec<String>,
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Path(page_id): axum::extract::Path<crate::page_id::PageId>,
+) -> Result<axum::Json<ApiGetResponse>, axum::http::StatusCode> {
+    super::spawn_blocking(move || {
+        // everything but the file comes from this one snapshot of the index
+        let index = state
+            .index()
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        let page_meta = index
+            .page_metas
+            .get(&page_id)
+            .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        // read before the content, so that a concurrent write results in a conflict on update
+        let modified = crate::page_io::PageIo::page_modified(&state.config, &page_id)
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        let content = crate::page_io::PageIo::read_page_raw_content(&state.config, &page_id)
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        let html = state
+            .html_cache
+            .get_or_render(&state.config, &page_id, |title| index.resolve_title(title))
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        Ok(axum::Json(ApiGetResponse {
+            backlinks: index
+                .backlinks
+                .get(&page_id)
+                .map(|set| set.iter().map(|id| id.to_string()).collect())
+                .unwrap_or_default(),
+            content,
+            html,
+            id: page_id.to_string(),
+            links: index
+                .links(&page_id)
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            modified: super::edit::format_modified(modified),
+            properties: page_meta.properties.clone(),
+            tags: page_meta.tags.iter().cloned().collect(),
+            title: page_meta.title.clone(),
+            title_links: page_meta.title_links.iter().cloned().collect(),
+        }))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn test_handle() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let page1_id = crate::test_util::create_page(
+            config.data_dir(),
+            "20251224T000000Z",
+            "# Page 1\n\n#rust [[Page 3]]\n",
+        )?;
+        let page2_id = crate::test_util::create_page(
+            config.data_dir(),
+            "20251224T000001Z",
+            "# Page 2\n\n[[20251224T000000Z]]\n",
+        )?;
+        let index = crate::index::Index::new(config.clone())?;
+        let router = crate::subcommand::serve::router(std::sync::Arc::new(
+            crate::subcommand::serve::State::new(config.clone(), index),
+        ));
+        let get = |uri: String| {
+            tower::ServiceExt::oneshot(
+                router.clone(),
+                axum::http::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = get(format!("/api/pages/{page1_id}")).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let json = serde_json::from_slice::<serde_json::Value>(&body)?;
+        assert_eq!(json["id"], page1_id.to_string());
+        assert_eq!(json["title"], "Page 1");
+        assert_eq!(json["content"], "# Page 1\n\n#rust [[Page 3]]\n");
+        assert!(
+            json["html"]
+                .as_str()
+                .is_some_and(|it| it.contains("<h1>Page 1</h1>"))
+        );
+        assert_eq!(json["backlinks"], serde_json::json!([page2_id.to_string()]));
+        assert_eq!(json["links"], serde_json::json!([]));
+        assert_eq!(json["tags"], serde_json::json!(["rust"]));
+        assert_eq!(json["title_links"], serde_json::json!(["Page 3"]));
+        let modified = crate::page_io::PageIo::page_modified(&config, &page1_id)?;
+        assert_eq!(
+            json["modified"],
+            super::super::edit::format_modified(modified)
+        );
+
+        let response = get(format!("/api/pages/{page2_id}")).await?;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let json = serde_json::from_slice::<serde_json::Value>(&body)?;
+        assert_eq!(json["backlinks"], serde_json::json!([]));
+        assert_eq!(json["links"], serde_json::json!([page1_id.to_string()]));
+
+        let response = get("/api/pages/20251224T000002Z".to_owned()).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+#[derive(serde::Serialize)]
+pub struct ApiListResponsePage {
+    pub(crate) backlinks: Vec<String>,
+    pub(crate) id: String,
+    pub(crate) links: Vec<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) title_links: Vec<String>,
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+) -> Result<axum::Json<Vec<ApiListResponsePage>>, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let pages = index
+        .page_metas
+        .iter()
+        .map(|(page_id, page_meta)| ApiListResponsePage {
+            backlinks: index
+                .backlinks
+                .get(page_id)
+                .map(|set| set.iter().map(|id| id.to_string()).collect())
+                .unwrap_or_default(),
+            id: page_id.to_string(),
+            links: index
+                .links(page_id)
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            title: page_meta.title.clone(),
+            title_links: page_meta.title_links.iter().cloned().collect(),
+        })
+        .collect::<Vec<ApiListResponsePage>>();
+    Ok(axum::Json(pages))
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn test_handle() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let page1_id = crate::test_util::create_page(
+            config.data_dir(),
+            "20251224T000000Z",
+            "# Page 1\n\n[[Page 3]]\n",
+        )?;
+        let page2_id = crate::test_util::create_page(
+            config.data_dir(),
+            "20251224T000001Z",
+            "# Page 2\n\n[[20251224T000000Z]]\n",
+        )?;
+        let index = crate::index::Index::new(config.clone())?;
+        let router = crate::subcommand::serve::router(std::sync::Arc::new(
+            crate::subcommand::serve::State::new(config, index),
+        ));
+
+        let response = tower::ServiceExt::oneshot(
+            router,
+            axum::http::Request::get("/api/pages")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body)?,
+            serde_json::json!([
+                {
+                    "backlinks": [page2_id.to_string()],
+                    "id": page1_id.to_string(),
+                    "links": [],
+                    "title": "Page 1",
+                    "title_links": ["Page 3"],
+                },
+                {
+                    "backlinks": [],
+                    "id": page2_id.to_string(),
+                    "links": [page1_id.to_string()],
+                    "title": "Page 2",
+                    "title_links": [],
+                },
+            ])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+#[derive(serde::Serialize)]
+pub struct ApiListTitlesResponseTitle {
+    pub(crate) page_ids: Vec<String>,
+    pub(crate) title: String,
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+) -> Result<axum::Json<Vec<ApiListTitlesResponseTitle>>, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let titles = index
+        .page_titles
+        .iter()
+        .map(|(title, page_ids)| ApiListTitlesResponseTitle {
+            page_ids: page_ids.iter().map(|id| id.to_string()).collect(),
+            title: title.to_owned(),
+        })
+        .collect::<Vec<ApiListTitlesResponseTitle>>();
+    Ok(axum::Json(titles))
+}
@@ -0,0 +1,18 @@
+#[derive(serde::Deserialize)]
+pub struct ApiSearchRequestQuery {
+    pub(crate) q: String,
+}
+
+/// Returns the pages that match `q` in descending order of score.
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Query(ApiSearchRequestQuery { q }): axum::extract::Query<ApiSearchRequestQuery>,
+) -> Result<axum::Json<Vec<super::list::ListResponsePageMeta>>, axum::http::StatusCode> {
+    let q = q.trim().to_owned();
+    if q.is_empty() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    super::spawn_blocking(move || Ok(axum::Json(super::list::build_page_metas(&state, &q)?))).await
+}
@@ -0,0 +1,102 @@
+#[derive(serde::Deserialize)]
+pub struct ApiUpdateRequestBody {
+    content: String,
+    /// The modification time returned by `GET /api/pages/{id}`
+    modified: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct ApiUpdateResponseBody {
+    id: String,
+    modified: String,
+}
+
+/// Returns `409 Conflict` if the page has been modified since `modified`.
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Path(page_id): axum::extract::Path<crate::page_id::PageId>,
+    axum::Json(ApiUpdateRequestBody { content, modified }): axum::Json<ApiUpdateRequestBody>,
+) -> Result<axum::Json<ApiUpdateResponseBody>, axum::http::StatusCode> {
+    let expected_modified =
+        super::edit::parse_modified(&modified).ok_or(axum::http::StatusCode::BAD_REQUEST)?;
+    super::spawn_blocking(move || {
+        let modified = super::update::update_page(&state, &page_id, &content, expected_modified)?
+            .map_err(|_| axum::http::StatusCode::CONFLICT)?;
+        Ok(axum::Json(ApiUpdateResponseBody {
+            id: page_id.to_string(),
+            modified: super::edit::format_modified(modified),
+        }))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn test_handle() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let page_id =
+            crate::test_util::create_page(config.data_dir(), "20251224T000000Z", "# Before\n")?;
+        // older than any write in this test, even with a coarse timestamp granularity
+        std::fs::File::options()
+            .write(true)
+            .open(crate::page_io::PageIo::page_path(&config, &page_id))?
+            .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000))?;
+        let index = crate::index::Index::new(config.clone())?;
+        let router = crate::subcommand::serve::router(std::sync::Arc::new(
+            crate::subcommand::serve::State::new(config, index),
+        ));
+        let uri = format!("/api/pages/{page_id}");
+        let get = || {
+            tower::ServiceExt::oneshot(
+                router.clone(),
+                axum::http::Request::get(&uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+        };
+        let put = |body: serde_json::Value| {
+            tower::ServiceExt::oneshot(
+                router.clone(),
+                axum::http::Request::put(&uri)
+                    .header(axum::http::header::CONTENT_TYPE, "application/json")
+                    .body(axum::body::Body::from(body.to_string()))
+                    .unwrap(),
+            )
+        };
+        let json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+            anyhow::Ok(serde_json::from_slice::<serde_json::Value>(&body)?)
+        };
+
+        let response = get().await?;
+        let modified = json(response).await?["modified"].clone();
+
+        let response =
+            put(serde_json::json!({ "content": "# After\n", "modified": modified })).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let updated = json(response).await?;
+        assert_eq!(updated["id"], page_id.to_string());
+        assert_ne!(updated["modified"], modified);
+
+        let response = get().await?;
+        let got = json(response).await?;
+        assert_eq!(got["content"], "# After\n");
+        assert_eq!(got["title"], "After");
+        assert_eq!(got["modified"], updated["modified"]);
+
+        // the page has been modified since `modified`
+        let response =
+            put(serde_json::json!({ "content": "# Stale\n", "modified": modified })).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::CONFLICT);
+        let response = get().await?;
+        assert_eq!(json(response).await?["content"], "# After\n");
+
+        let response =
+            put(serde_json::json!({ "content": "# Bad\n", "modified": "yesterday" })).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+}
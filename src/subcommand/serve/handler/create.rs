@@ -0,0 +1,82 @@
+#[derive(serde::Deserialize)]
+pub struct CreateRequestBody {
+    /// The page ID to link back to from the new page
+    from: Option<String>,
+    /// The title of the new page
+    title: Option<String>,
+}
+
+pub struct CreateResponse(crate::page_id::PageId);
+
+impl axum::response::IntoResponse for CreateResponse {
+    fn into_response(self) -> axum::response::Response {
+        match axum::http::HeaderValue::from_str(&format!("/pages/{}/edit", self.0)) {
+            Ok(location) => (
+                axum::http::StatusCode::SEE_OTHER,
+                [(axum::http::header::LOCATION, location)],
+            )
+                .into_response(),
+            Err(_) => axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    headers: axum::http::HeaderMap,
+    axum::extract::Form(CreateRequestBody { from, title }): axum::extract::Form<CreateRequestBody>,
+) -> Result<CreateResponse, axum::http::StatusCode> {
+    super::check_same_origin(&headers)?;
+    let from = match from.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(from) => Some(
+            <crate::page_id::PageId as std::str::FromStr>::from_str(from)
+                .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?,
+        ),
+    };
+    let content = new_page_content(title.as_deref(), from.as_ref());
+    let page_id = super::spawn_blocking(move || create_page(&state, &content)).await?;
+    Ok(CreateResponse(page_id))
+}
+
+/// Returns the initial content of a new page with the title and a link back to `from`.
+pub(crate) fn new_page_content(
+    title: Option<&str>,
+    from: Option<&crate::page_id::PageId>,
+) -> String {
+    let title = title.map(str::trim).filter(|title| !title.is_empty());
+    let mut content = String::new();
+    if let Some(title) = title {
+        content.push_str(&format!("# {title}\n"));
+    }
+    if let Some(from) = from {
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(&format!("[{from}]\n"));
+    }
+    content
+}
+
+/// Creates a page with a new ID and adds it to the index.
+///
+/// Returns `409 Conflict` if a page has already been created in the same second.
+pub(crate) fn create_page(
+    state: &crate::subcommand::serve::State,
+    content: &str,
+) -> Result<crate::page_id::PageId, axum::http::StatusCode> {
+    let page_id = crate::page_id::PageId::new();
+    state
+        .modify_page(&page_id, |config| {
+            crate::page_io::PageIo::create_page(config, &page_id, content)
+        })
+        .map_err(|e| match e.downcast_ref::<std::io::Error>() {
+            Some(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                axum::http::StatusCode::CONFLICT
+            }
+            _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+    Ok(page_id)
+}
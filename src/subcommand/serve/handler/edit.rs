@@ -0,0 +1,73 @@
+#[derive(Debug, askama::Template)]
+#[template(path = "edit.html")]
+pub struct EditResponse {
+    /// The content of the page on the disk, set if the save was rejected
+    pub(crate) conflict: Option<String>,
+    pub(crate) content: String,
+    pub(crate) id: String,
+    /// The modification time of the page when the form was loaded
+    pub(crate) modified: String,
+    pub(crate) title: String,
+}
+
+impl axum::response::IntoResponse for EditResponse {
+    fn into_response(self) -> axum::response::Response {
+        let status = match self.conflict {
+            None => axum::http::StatusCode::OK,
+            Some(_) => axum::http::StatusCode::CONFLICT,
+        };
+        let body = self.to_string();
+        (status, axum::response::Html(body)).into_response()
+    }
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Path(page_id): axum::extract::Path<crate::page_id::PageId>,
+) -> Result<EditResponse, axum::http::StatusCode> {
+    super::spawn_blocking(move || {
+        let modified = crate::page_io::PageIo::page_modified(&state.config, &page_id)
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        let content = crate::page_io::PageIo::read_page_raw_content(&state.config, &page_id)
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        Ok(EditResponse {
+            conflict: None,
+            content,
+            id: page_id.to_string(),
+            modified: format_modified(modified),
+            title: page_title(&state, &page_id)?,
+        })
+    })
+    .await
+}
+
+/// Returns the title of the page in the index, or an empty string if it has no title.
+pub(crate) fn page_title(
+    state: &crate::subcommand::serve::State,
+    page_id: &crate::page_id::PageId,
+) -> Result<String, axum::http::StatusCode> {
+    Ok(state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .page_metas
+        .get(page_id)
+        .and_then(|it| it.title.clone())
+        .unwrap_or_default())
+}
+
+/// Formats the modification time as nanoseconds since the UNIX epoch.
+pub(crate) fn format_modified(modified: std::time::SystemTime) -> String {
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// Parses the modification time formatted by [`format_modified`].
+pub(crate) fn parse_modified(s: &str) -> Option<std::time::SystemTime> {
+    let nanos = s.parse::<u64>().ok()?;
+    std::time::UNIX_EPOCH.checked_add(std::time::Duration::from_nanos(nanos))
+}
@@ -0,0 +1,20 @@
+/// Streams page change events as Server-Sent Events.
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+) -> axum::response::sse::Sse<
+    impl tokio_stream::Stream<Item = Result<axum::response::sse::Event, axum::Error>>,
+> {
+    let receiver = state.events.subscribe();
+    // lagged receivers skip the missed events
+    let stream = tokio_stream::StreamExt::filter_map(
+        tokio_stream::wrappers::BroadcastStream::new(receiver),
+        |result| {
+            result
+                .ok()
+                .map(|page_changed| axum::response::sse::Event::default().json_data(page_changed))
+        },
+    );
+    axum::response::sse::Sse::new(stream).keep_alive(axum::response::sse::KeepAlive::default())
+}
@@ -1,10 +1,32 @@
 #[derive(Debug, askama::Template)]
 #[template(path = "get.html")]
 pub struct GetResponse {
+    /// The alias by which the page was found (`/titles/{alias}`)
+    pub(crate) alias: Option<String>,
     pub(crate) backlinks: Vec<(String, String)>,
     pub(crate) html: String,
     pub(crate) id: String,
+    /// The existing pages linked from the page
+    pub(crate) links: Vec<(String, String)>,
+    /// The linked page IDs without a page
+    pub(crate) missing_page_ids: Vec<String>,
+    /// The linked titles that no page has
+    pub(crate) missing_titles: Vec<String>,
+    pub(crate) tags: Vec<String>,
     pub(crate) title: String,
+    /// The pages that link to the same pages as the page, grouped by the shared target
+    pub(crate) two_hop_links: Vec<GetResponseTwoHopLinks>,
+}
+
+#[derive(Debug)]
+pub struct GetResponseTwoHopLinks {
+    pub(crate) pages: Vec<(String, String)>,
+    pub(crate) target: (String, String),
+}
+
+#[derive(serde::Deserialize)]
+pub struct GetRequestQuery {
+    alias: Option<String>,
 }
 
 impl axum::response::IntoResponse for GetResponse {
@@ -16,43 +38,80 @@ impl axum::response::IntoResponse for GetResponse {
 
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
     axum::extract::Path(page_id): axum::extract::Path<crate::page_id::PageId>,
+    axum::extract::Query(GetRequestQuery { alias }): axum::extract::Query<GetRequestQuery>,
 ) -> Result<GetResponse, axum::http::StatusCode> {
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-    let page_meta = state
-        .index
+    super::spawn_blocking(move || build_response(&state, &page_id, alias.as_deref())).await
+}
+
+/// `alias` is shown only if it is an alias of the page other than its title.
+pub(crate) fn build_response(
+    state: &crate::subcommand::serve::State,
+    page_id: &crate::page_id::PageId,
+    alias: Option<&str>,
+) -> Result<GetResponse, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let page_meta = index
         .page_metas
-        .get(&page_id)
+        .get(page_id)
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
-    let html = crate::page_io::PageIo::read_page_content(&state.config, &page_id)
+    let html = state
+        .html_cache
+        .get_or_render(&state.config, page_id, |title| index.resolve_title(title))
         .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
 
+    // (ID, title)
+    let page = |id: &crate::page_id::PageId| -> (String, String) {
+        (
+            id.to_string(),
+            index
+                .page_metas
+                .get(id)
+                .and_then(|it| it.title.clone())
+                .unwrap_or_default(),
+        )
+    };
+    let (missing_page_ids, missing_titles) = index.missing_links(page_id);
+
     Ok(GetResponse {
-        backlinks: state
-            .index
-            .backlinks
-            .get(&page_id)
-            .map(|set| {
-                set.iter()
-                    .map(|id| -> (String, String) {
-                        (
-                            id.to_string(),
-                            state
-                                .index
-                                .page_metas
-                                .get(id)
-                                .and_then(|it| it.title.clone())
-                                .unwrap_or_default(),
-                        )
-                    })
-                    .collect::<Vec<(String, String)>>()
+        alias: alias
+            .filter(|alias| {
+                page_meta.properties.aliases.iter().any(|it| it == alias)
+                    && page_meta.title.as_deref() != Some(*alias)
             })
+            .map(str::to_owned),
+        backlinks: index
+            .backlinks
+            .get(page_id)
+            .map(|set| set.iter().map(page).collect::<Vec<(String, String)>>())
             .unwrap_or_default(),
         html,
         id: page_id.to_string(),
+        links: index
+            .links(page_id)
+            .iter()
+            .filter(|id| index.page_metas.contains_key(id))
+            .map(page)
+            .collect::<Vec<(String, String)>>(),
+        missing_page_ids: missing_page_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>(),
+        missing_titles: missing_titles.into_iter().collect::<Vec<String>>(),
+        tags: page_meta.tags.iter().cloned().collect(),
         title: page_meta.title.clone().unwrap_or_default(),
+        two_hop_links: index
+            .two_hop_links(page_id)
+            .iter()
+            .map(|(target, page_ids)| GetResponseTwoHopLinks {
+                pages: page_ids.iter().map(page).collect::<Vec<(String, String)>>(),
+                target: page(target),
+            })
+            .collect::<Vec<GetResponseTwoHopLinks>>(),
     })
 }
@@ -1,29 +1,226 @@
+/// Serves the image file.
+///
+/// The `Content-Type` is guessed from the extension. `Last-Modified`, `ETag` (derived from the
+/// modification time and the size), conditional requests and `Range` requests are supported,
+/// and the file is streamed instead of being read into memory.
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
     axum::extract::Path(image_name): axum::extract::Path<String>,
-) -> Result<Vec<u8>, axum::http::StatusCode> {
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-    let images_dir = state
-        .config
-        .images_dir()
-        .canonicalize()
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
-    let image_file_path = images_dir
-        .join(&image_name)
-        .canonicalize()
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
-    if !image_file_path.starts_with(images_dir) {
-        return Err(axum::http::StatusCode::FORBIDDEN);
-    }
+    mut request: axum::extract::Request,
+) -> Result<axum::response::Response, axum::http::StatusCode> {
+    let (image_file_path, etag) = super::spawn_blocking(move || {
+        let images_dir = state
+            .config
+            .images_dir()
+            .canonicalize()
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        let image_file_path = images_dir
+            .join(&image_name)
+            .canonicalize()
+            .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        if !image_file_path.starts_with(images_dir) {
+            return Err(axum::http::StatusCode::FORBIDDEN);
+        }
+        let metadata =
+            std::fs::metadata(&image_file_path).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+        if !metadata.is_file() {
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+        let etag = etag(&metadata);
+        Ok((image_file_path, etag))
+    })
+    .await?;
 
-    if !std::fs::exists(&image_file_path)
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
-    {
-        return Err(axum::http::StatusCode::NOT_FOUND);
+    let if_none_match = request
+        .headers()
+        .get(axum::http::header::IF_NONE_MATCH)
+        .and_then(|it| it.to_str().ok())
+        .map(str::to_owned);
+    let mut response = match (if_none_match, &etag) {
+        (Some(if_none_match), Some(etag))
+            if crate::subcommand::serve::etag::matches_etag(&if_none_match, etag) =>
+        {
+            axum::response::IntoResponse::into_response(axum::http::StatusCode::NOT_MODIFIED)
+        }
+        (if_none_match, _) => {
+            // `If-None-Match` takes precedence over `If-Modified-Since` (RFC 9110 13.1.3)
+            if if_none_match.is_some() {
+                request
+                    .headers_mut()
+                    .remove(axum::http::header::IF_MODIFIED_SINCE);
+            }
+            tower::ServiceExt::oneshot(
+                tower_http::services::ServeFile::new(image_file_path),
+                request,
+            )
+            .await
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+            .map(axum::body::Body::new)
+        }
+    };
+    // revalidate with `If-None-Match` or `If-Modified-Since` instead of using a stale image
+    if response.status().is_success() || response.status() == axum::http::StatusCode::NOT_MODIFIED {
+        if let Some(etag) = etag.and_then(|it| axum::http::HeaderValue::from_str(&it).ok()) {
+            response
+                .headers_mut()
+                .insert(axum::http::header::ETAG, etag);
+        }
+        response.headers_mut().insert(
+            axum::http::header::CACHE_CONTROL,
+            axum::http::HeaderValue::from_static("no-cache"),
+        );
     }
+    Ok(response)
+}
+
+/// Returns a strong `ETag` derived from the modification time and the size of the file, or
+/// `None` if the modification time is not available.
+///
+/// Images are served as is (not compressed), so the same `ETag` means the same bytes.
+fn etag(metadata: &std::fs::Metadata) -> Option<String> {
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    Some(format!(
+        "\"{:x}-{:x}\"",
+        modified.as_nanos(),
+        metadata.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        std::fs::create_dir_all(config.images_dir())?;
+        let image_file_path = config.images_dir().join("image.png");
+        std::fs::write(&image_file_path, "0123456789")?;
+        let index = crate::index::Index::new_without_terms(config.clone())?;
+        let router = axum::Router::new()
+            .route("/images/{image_name}", axum::routing::get(handle))
+            .with_state(std::sync::Arc::new(crate::subcommand::serve::State::new(
+                config, index,
+            )));
+        let get = |uri: &str, headers: &[(axum::http::HeaderName, &str)]| {
+            let mut request = axum::http::Request::get(uri);
+            for (name, value) in headers {
+                request = request.header(name, *value);
+            }
+            tower::ServiceExt::oneshot(
+                router.clone(),
+                request.body(axum::body::Body::empty()).unwrap(),
+            )
+        };
+        let header = |response: &axum::response::Response, name: axum::http::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|it| it.to_str().ok())
+                .map(str::to_owned)
+        };
+
+        let response = get("/images/image.png", &[]).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            header(&response, axum::http::header::CONTENT_TYPE).as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(
+            header(&response, axum::http::header::CACHE_CONTROL).as_deref(),
+            Some("no-cache")
+        );
+        let last_modified = header(&response, axum::http::header::LAST_MODIFIED)
+            .ok_or_else(|| anyhow::anyhow!("no Last-Modified"))?;
+        let etag = header(&response, axum::http::header::ETAG)
+            .ok_or_else(|| anyhow::anyhow!("no ETag"))?;
+        assert_eq!(
+            axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+            "0123456789"
+        );
 
-    Ok(std::fs::read(&image_file_path)
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?)
+        let response = get(
+            "/images/image.png",
+            &[(axum::http::header::RANGE, "bytes=2-4")],
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            header(&response, axum::http::header::CONTENT_RANGE).as_deref(),
+            Some("bytes 2-4/10")
+        );
+        assert_eq!(
+            axum::body::to_bytes(response.into_body(), usize::MAX).await?,
+            "234"
+        );
+
+        let response = get(
+            "/images/image.png",
+            &[(axum::http::header::IF_MODIFIED_SINCE, &last_modified)],
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            header(&response, axum::http::header::CACHE_CONTROL).as_deref(),
+            Some("no-cache")
+        );
+
+        let response = get(
+            "/images/image.png",
+            &[(axum::http::header::IF_NONE_MATCH, &etag)],
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            header(&response, axum::http::header::ETAG).as_deref(),
+            Some(etag.as_str())
+        );
+        assert_eq!(
+            header(&response, axum::http::header::CACHE_CONTROL).as_deref(),
+            Some("no-cache")
+        );
+
+        // `If-None-Match` takes precedence over `If-Modified-Since`
+        let response = get(
+            "/images/image.png",
+            &[
+                (axum::http::header::IF_NONE_MATCH, "\"other\""),
+                (axum::http::header::IF_MODIFIED_SINCE, &last_modified),
+            ],
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+
+        std::fs::write(&image_file_path, "01234567890")?;
+        let response = get(
+            "/images/image.png",
+            &[(axum::http::header::IF_NONE_MATCH, &etag)],
+        )
+        .await?;
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_ne!(header(&response, axum::http::header::ETAG), Some(etag));
+
+        // errors are not cached as images
+        let response = get(
+            "/images/image.png",
+            &[(axum::http::header::RANGE, "bytes=20-30")],
+        )
+        .await?;
+        assert_eq!(
+            response.status(),
+            axum::http::StatusCode::RANGE_NOT_SATISFIABLE
+        );
+        assert_eq!(header(&response, axum::http::header::CACHE_CONTROL), None);
+
+        let response = get("/images/missing.png", &[]).await?;
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+        assert_eq!(header(&response, axum::http::header::CACHE_CONTROL), None);
+        Ok(())
+    }
 }
@@ -1,30 +1,142 @@
-pub struct GetPageByTitleResponse(crate::page_id::PageId);
+/// The number of lines shown for each page on the disambiguation page
+const EXCERPT_LINES: usize = 3;
+
+pub enum GetPageByTitleResponse {
+    Disambiguation(GetPageByTitleDisambiguation),
+    /// Redirects to the page. The alias is passed to the page if the title is an alias.
+    Redirect(crate::page_id::PageId, Option<String>),
+}
 
 impl axum::response::IntoResponse for GetPageByTitleResponse {
     fn into_response(self) -> axum::response::Response {
-        let mut response = axum::http::StatusCode::FOUND.into_response();
-        response.headers_mut().insert(
-            axum::http::header::LOCATION,
-            // FIXME: unwrap
-            axum::http::HeaderValue::from_str(&format!("/pages/{}", self.0)).unwrap(),
-        );
-        response
+        match self {
+            GetPageByTitleResponse::Disambiguation(disambiguation) => {
+                let body = disambiguation.to_string();
+                axum::response::Html(body).into_response()
+            }
+            GetPageByTitleResponse::Redirect(page_id, alias) => {
+                let location = match alias {
+                    None => format!("/pages/{}", page_id),
+                    Some(alias) => format!(
+                        "/pages/{}?alias={}",
+                        page_id,
+                        crate::util::percent_encode(&alias)
+                    ),
+                };
+                match axum::http::HeaderValue::from_str(&location) {
+                    Ok(location) => (
+                        axum::http::StatusCode::FOUND,
+                        [(axum::http::header::LOCATION, location)],
+                    )
+                        .into_response(),
+                    Err(_) => axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                }
+            }
+        }
     }
 }
 
+/// The list of pages shown when several pages share the title
+#[derive(askama::Template)]
+#[template(path = "get_page_by_title.html")]
+pub struct GetPageByTitleDisambiguation {
+    pub(crate) pages: Vec<GetPageByTitleDisambiguationPage>,
+    pub(crate) title: String,
+}
+
+pub struct GetPageByTitleDisambiguationPage {
+    pub(crate) created_at: Option<String>,
+    pub(crate) excerpt: Vec<String>,
+    pub(crate) id: String,
+    /// `true` if the requested title is an alias of the page
+    pub(crate) is_alias: bool,
+    pub(crate) title: Option<String>,
+}
+
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
     axum::extract::Path(title): axum::extract::Path<String>,
 ) -> Result<GetPageByTitleResponse, axum::http::StatusCode> {
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-    let page_ids = state
-        .index
-        .page_titles
-        .get(&title)
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
-    // FIXME: select page_id
-    let page_id = page_ids.first().ok_or(axum::http::StatusCode::NOT_FOUND)?;
-    Ok(GetPageByTitleResponse(page_id.clone()))
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let page_ids = index.resolve_title(&title);
+    match page_ids.as_slice() {
+        [] => Err(axum::http::StatusCode::NOT_FOUND),
+        [page_id] => Ok(GetPageByTitleResponse::Redirect(
+            page_id.clone(),
+            is_alias(&index, page_id, &title).then(|| title.clone()),
+        )),
+        _ => {
+            let candidates = candidates(&index, &page_ids, &title);
+            drop(index);
+            super::spawn_blocking(move || {
+                let pages = candidates
+                    .into_iter()
+                    .map(|(page_id, is_alias, page_title)| {
+                        disambiguation_page(&state.config, &page_id, is_alias, page_title)
+                    })
+                    .collect::<Result<Vec<GetPageByTitleDisambiguationPage>, _>>()?;
+                Ok(GetPageByTitleResponse::Disambiguation(
+                    GetPageByTitleDisambiguation { pages, title },
+                ))
+            })
+            .await
+        }
+    }
+}
+
+/// Returns `true` if `title` is an alias of the page (pages without a H1 are found only by
+/// aliases).
+fn is_alias(index: &crate::index::Index, page_id: &crate::page_id::PageId, title: &str) -> bool {
+    index.page_metas.get(page_id).is_some_and(|page_meta| {
+        page_meta.properties.aliases.iter().any(|it| it == title)
+            && page_meta.title.as_deref() != Some(title)
+    })
+}
+
+/// Returns the pages with whether `title` is their alias and their own title.
+fn candidates(
+    index: &crate::index::Index,
+    page_ids: &[crate::page_id::PageId],
+    title: &str,
+) -> Vec<(crate::page_id::PageId, bool, Option<String>)> {
+    page_ids
+        .iter()
+        .map(|page_id| {
+            let page_title = index
+                .page_metas
+                .get(page_id)
+                .and_then(|page_meta| page_meta.title.clone());
+            (page_id.clone(), is_alias(index, page_id, title), page_title)
+        })
+        .collect()
+}
+
+fn disambiguation_page(
+    config: &crate::config::Config,
+    page_id: &crate::page_id::PageId,
+    is_alias: bool,
+    title: Option<String>,
+) -> Result<GetPageByTitleDisambiguationPage, axum::http::StatusCode> {
+    let content = crate::page_io::PageIo::read_page_raw_content(config, page_id)
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(GetPageByTitleDisambiguationPage {
+        created_at: page_id
+            .created_at()
+            .map(|it| it.format("%Y-%m-%d %H:%M:%S").to_string()),
+        excerpt: crate::page_meta::PageProperties::split_front_matter(&content)
+            .1
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("# "))
+            .take(EXCERPT_LINES)
+            .map(str::to_owned)
+            .collect::<Vec<String>>(),
+        id: page_id.to_string(),
+        is_alias,
+        title,
+    })
 }
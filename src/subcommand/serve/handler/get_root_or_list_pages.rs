@@ -1,11 +1,11 @@
 pub enum GetRootOrListPagesResponse {
-    Get(super::get::GetResponse),
+    Get(Box<super::get::GetResponse>),
     List,
 }
 
 impl From<super::get::GetResponse> for GetRootOrListPagesResponse {
     fn from(value: super::get::GetResponse) -> Self {
-        Self::Get(value)
+        Self::Get(Box::new(value))
     }
 }
 
@@ -27,43 +27,21 @@ impl axum::response::IntoResponse for GetRootOrListPagesResponse {
 
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
 ) -> Result<GetRootOrListPagesResponse, axum::http::StatusCode> {
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-
-    let page_id = crate::page_id::PageId::root();
-    match state.index.page_metas.get(&page_id) {
-        Some(page_meta) => {
-            let html = crate::page_io::PageIo::read_page_content(&state.config, &page_id)
-                .map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
-
-            Ok(GetRootOrListPagesResponse::from(super::get::GetResponse {
-                backlinks: state
-                    .index
-                    .backlinks
-                    .get(&page_id)
-                    .map(|set| {
-                        set.iter()
-                            .map(|id| -> (String, String) {
-                                (
-                                    id.to_string(),
-                                    state
-                                        .index
-                                        .page_metas
-                                        .get(id)
-                                        .and_then(|it| it.title.clone())
-                                        .unwrap_or_default(),
-                                )
-                            })
-                            .collect::<Vec<(String, String)>>()
-                    })
-                    .unwrap_or_default(),
-                html,
-                id: page_id.to_string(),
-                title: page_meta.title.clone().unwrap_or_default(),
-            }))
+    super::spawn_blocking(move || {
+        let page_id = crate::page_id::PageId::root();
+        let index = state
+            .index()
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        if index.page_metas.contains_key(&page_id) {
+            Ok(GetRootOrListPagesResponse::from(
+                super::get::build_response(&state, &page_id, None)?,
+            ))
+        } else {
+            Ok(GetRootOrListPagesResponse::List)
         }
-        None => Ok(GetRootOrListPagesResponse::List),
-    }
+    })
+    .await
 }
@@ -0,0 +1,42 @@
+#[derive(askama::Template)]
+#[template(path = "get_tag.html")]
+pub struct GetTagResponse {
+    pub(crate) pages: Vec<(String, String)>,
+    pub(crate) tag: String,
+}
+
+impl axum::response::IntoResponse for GetTagResponse {
+    fn into_response(self) -> axum::response::Response {
+        let body = self.to_string();
+        axum::response::Html(body).into_response()
+    }
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Path(tag): axum::extract::Path<String>,
+) -> Result<GetTagResponse, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let page_ids = index
+        .tags
+        .get(&tag)
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let pages = page_ids
+        .iter()
+        .map(|id| {
+            (
+                id.to_string(),
+                index
+                    .page_metas
+                    .get(id)
+                    .and_then(|it| it.title.clone())
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<(String, String)>>();
+    Ok(GetTagResponse { pages, tag })
+}
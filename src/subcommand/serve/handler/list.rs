@@ -1,3 +1,6 @@
+/// The maximum number of snippets per page
+const SNIPPETS: usize = 3;
+
 #[derive(askama::Template)]
 #[template(path = "list.html")]
 pub struct ListResponse {
@@ -12,8 +15,11 @@ impl axum::response::IntoResponse for ListResponse {
     }
 }
 
+#[derive(serde::Serialize)]
 pub struct ListResponsePageMeta {
     pub(crate) id: String,
+    pub(crate) score: Option<f64>,
+    pub(crate) snippets: Vec<Vec<crate::search::SnippetFragment>>,
     pub(crate) title: String,
 }
 
@@ -24,55 +30,63 @@ pub struct ListRequestQuery {
 
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
     axum::extract::Query(ListRequestQuery { q }): axum::extract::Query<ListRequestQuery>,
 ) -> Result<ListResponse, axum::http::StatusCode> {
     let q = q.unwrap_or_default().trim().to_owned();
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-    let config = &state.config;
-    let page_metas = state
-        .index
-        .page_metas
-        .iter()
-        .filter(|(page_id, _page_meta)| {
-            q.is_empty() || {
-                crate::page_io::PageIo::read_page_content(config, page_id)
-                    .is_ok_and(|content| match_content(&content, &q))
-            }
-        })
-        .map(|(id, meta)| ListResponsePageMeta {
-            id: id.to_string(),
-            title: meta.title.clone().unwrap_or_default(),
-        })
-        .collect::<Vec<ListResponsePageMeta>>();
-    Ok(ListResponse { page_metas, q })
-}
-
-fn match_content(content: &str, q: &str) -> bool {
-    let content = content.to_lowercase();
-    let q = q.to_lowercase();
-    q.split_whitespace().all(|q| content.contains(q))
+    super::spawn_blocking(move || {
+        let page_metas = build_page_metas(&state, &q)?;
+        Ok(ListResponse { page_metas, q })
+    })
+    .await
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_match_content() {
-        let content = "This is a sample page content.";
-        // single keyword
-        assert!(match_content(content, "This"));
-        assert!(match_content(content, "page"));
-        // ignore case
-        assert!(match_content(content, "this"));
-        // multiple keywords
-        assert!(match_content(content, "This page"));
-        assert!(match_content(content, "This\tpage"));
-        assert!(match_content(content, "This\u{3000}page"));
-        assert!(match_content(content, "This \t\u{3000}page"));
-        // not found
-        assert!(!match_content(content, "notfound"));
+/// Returns all pages if `q` is empty. Otherwise, returns the pages that match `q` in
+/// descending order of score.
+pub(crate) fn build_page_metas(
+    state: &crate::subcommand::serve::State,
+    q: &str,
+) -> Result<Vec<ListResponsePageMeta>, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    if q.is_empty() {
+        Ok(index
+            .page_metas
+            .iter()
+            .map(|(id, meta)| ListResponsePageMeta {
+                id: id.to_string(),
+                score: None,
+                snippets: vec![],
+                title: meta.title.clone().unwrap_or_default(),
+            })
+            .collect::<Vec<ListResponsePageMeta>>())
+    } else {
+        let search_config = state.config.search();
+        let keywords = crate::search::Query::parse(q, &search_config).keywords();
+        Ok(index
+            .search(q)
+            .into_iter()
+            .map(|(id, score)| ListResponsePageMeta {
+                id: id.to_string(),
+                score: Some(score),
+                snippets: crate::page_io::PageIo::read_page_raw_content(&state.config, &id)
+                    .map(|content| {
+                        crate::search::snippets(
+                            crate::page_meta::PageProperties::split_front_matter(&content).1,
+                            &keywords,
+                            &search_config,
+                            SNIPPETS,
+                        )
+                    })
+                    .unwrap_or_default(),
+                title: index
+                    .page_metas
+                    .get(&id)
+                    .and_then(|meta| meta.title.clone())
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<ListResponsePageMeta>>())
     }
 }
@@ -0,0 +1,36 @@
+#[derive(askama::Template)]
+#[template(path = "list_tags.html")]
+pub struct ListTagsResponse {
+    pub(crate) tags: Vec<ListTagsResponseTag>,
+}
+
+impl axum::response::IntoResponse for ListTagsResponse {
+    fn into_response(self) -> axum::response::Response {
+        let body = self.to_string();
+        axum::response::Html(body).into_response()
+    }
+}
+
+pub struct ListTagsResponseTag {
+    pub(crate) pages: usize,
+    pub(crate) value: String,
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+) -> Result<ListTagsResponse, axum::http::StatusCode> {
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tags = index
+        .tags
+        .iter()
+        .map(|(tag, page_ids)| ListTagsResponseTag {
+            pages: page_ids.len(),
+            value: tag.to_owned(),
+        })
+        .collect::<Vec<ListTagsResponseTag>>();
+    Ok(ListTagsResponse { tags })
+}
@@ -18,12 +18,13 @@ pub struct ListTitlesResponsePageTitle {
 
 pub async fn handle(
     axum::extract::State(state): axum::extract::State<
-        std::sync::Arc<std::sync::Mutex<crate::subcommand::serve::State>>,
+        std::sync::Arc<crate::subcommand::serve::State>,
     >,
 ) -> Result<ListTitlesResponse, axum::http::StatusCode> {
-    let state = state.lock().map_err(|_| axum::http::StatusCode::CONFLICT)?;
-    let page_titles = state
-        .index
+    let index = state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let page_titles = index
         .page_titles
         .iter()
         .map(|(title, page_ids)| ListTitlesResponsePageTitle {
@@ -0,0 +1,94 @@
+#[derive(serde::Deserialize)]
+pub struct UpdateRequestBody {
+    content: String,
+    modified: String,
+}
+
+pub enum UpdateResponse {
+    Conflict(super::edit::EditResponse),
+    Updated(crate::page_id::PageId),
+}
+
+impl axum::response::IntoResponse for UpdateResponse {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            UpdateResponse::Conflict(edit_response) => edit_response.into_response(),
+            UpdateResponse::Updated(page_id) => {
+                match axum::http::HeaderValue::from_str(&format!("/pages/{}", page_id)) {
+                    Ok(location) => (
+                        axum::http::StatusCode::SEE_OTHER,
+                        [(axum::http::header::LOCATION, location)],
+                    )
+                        .into_response(),
+                    Err(_) => axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                }
+            }
+        }
+    }
+}
+
+pub async fn handle(
+    axum::extract::State(state): axum::extract::State<
+        std::sync::Arc<crate::subcommand::serve::State>,
+    >,
+    axum::extract::Path(page_id): axum::extract::Path<crate::page_id::PageId>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Form(UpdateRequestBody { content, modified }): axum::extract::Form<
+        UpdateRequestBody,
+    >,
+) -> Result<UpdateResponse, axum::http::StatusCode> {
+    super::check_same_origin(&headers)?;
+    let expected_modified =
+        super::edit::parse_modified(&modified).ok_or(axum::http::StatusCode::BAD_REQUEST)?;
+    // browsers submit textarea line breaks as CRLF
+    let content = content.replace("\r\n", "\n");
+
+    super::spawn_blocking(move || {
+        match update_page(&state, &page_id, &content, expected_modified)? {
+            Ok(_) => Ok(UpdateResponse::Updated(page_id)),
+            Err(crate::page_io::PageConflict) => {
+                // keep the submitted content so that it can be merged by hand
+                let modified = crate::page_io::PageIo::page_modified(&state.config, &page_id)
+                    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                let current =
+                    crate::page_io::PageIo::read_page_raw_content(&state.config, &page_id)
+                        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                Ok(UpdateResponse::Conflict(super::edit::EditResponse {
+                    conflict: Some(current),
+                    content,
+                    id: page_id.to_string(),
+                    modified: super::edit::format_modified(modified),
+                    title: super::edit::page_title(&state, &page_id)?,
+                }))
+            }
+        }
+    })
+    .await
+}
+
+/// Writes the page and updates the index. Returns the new modification time, or
+/// [`crate::page_io::PageConflict`] if the page has been modified since `expected_modified`.
+pub(crate) fn update_page(
+    state: &crate::subcommand::serve::State,
+    page_id: &crate::page_id::PageId,
+    content: &str,
+    expected_modified: std::time::SystemTime,
+) -> Result<Result<std::time::SystemTime, crate::page_io::PageConflict>, axum::http::StatusCode> {
+    if !state
+        .index()
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .page_metas
+        .contains_key(page_id)
+    {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+    match state.modify_page(page_id, |config| {
+        crate::page_io::PageIo::write_page(config, page_id, content, expected_modified)
+    }) {
+        Ok(modified) => Ok(Ok(modified)),
+        Err(e) => match e.downcast::<crate::page_io::PageConflict>() {
+            Ok(conflict) => Ok(Err(conflict)),
+            Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+        },
+    }
+}
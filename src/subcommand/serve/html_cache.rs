@@ -0,0 +1,138 @@
+/// A cache of rendered page HTML keyed by page ID and file modification time.
+///
+/// The cache can be shared between threads. Pages are rendered without holding the lock.
+#[derive(Default)]
+pub struct HtmlCache {
+    entries: std::sync::Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// Incremented on every invalidation, so that HTML rendered with an outdated index is not
+    /// cached after the invalidation
+    generation: u64,
+    pages: std::collections::BTreeMap<crate::page_id::PageId, (std::time::SystemTime, String)>,
+}
+
+impl HtmlCache {
+    pub fn clear(&self) {
+        let mut entries = self.entries();
+        entries.generation += 1;
+        entries.pages.clear();
+    }
+
+    /// Returns the cached HTML if the file has not been modified since it was rendered.
+    /// Otherwise, renders the page and caches the result.
+    pub fn get_or_render<F>(
+        &self,
+        config: &crate::config::Config,
+        page_id: &crate::page_id::PageId,
+        resolve_title: F,
+    ) -> anyhow::Result<String>
+    where
+        F: Fn(&str) -> Vec<crate::page_id::PageId>,
+    {
+        let modified = crate::page_io::PageIo::page_modified(config, page_id)?;
+        let generation = {
+            let entries = self.entries();
+            if let Some((cached_modified, html)) = entries.pages.get(page_id)
+                && *cached_modified == modified
+            {
+                return Ok(html.clone());
+            }
+            entries.generation
+        };
+        let html = crate::page_io::PageIo::read_page_content(config, page_id, resolve_title)?;
+        let mut entries = self.entries();
+        if entries.generation == generation {
+            entries
+                .pages
+                .insert(page_id.clone(), (modified, html.clone()));
+        }
+        Ok(html)
+    }
+
+    pub fn remove(&self, page_id: &crate::page_id::PageId) {
+        let mut entries = self.entries();
+        entries.generation += 1;
+        entries.pages.remove(page_id);
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // the entries are always consistent, so a poisoned lock can be recovered
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_render() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let page_id =
+            crate::test_util::create_page(config.data_dir(), "20251224T000000Z", "# Title 1\n")?;
+        let path = crate::page_io::PageIo::page_path(&config, &page_id);
+
+        let html_cache = HtmlCache::default();
+        assert_eq!(
+            html_cache.get_or_render(&config, &page_id, |_| vec![])?,
+            "<h1>Title 1</h1>\n"
+        );
+
+        // cached while the modification time is unchanged
+        let modified = std::fs::metadata(&path)?.modified()?;
+        std::fs::write(&path, "# Title 2\n")?;
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(modified)?;
+        assert_eq!(
+            html_cache.get_or_render(&config, &page_id, |_| vec![])?,
+            "<h1>Title 1</h1>\n"
+        );
+
+        html_cache.remove(&page_id);
+        assert_eq!(
+            html_cache.get_or_render(&config, &page_id, |_| vec![])?,
+            "<h1>Title 2</h1>\n"
+        );
+
+        // re-rendered when the modification time is changed
+        std::fs::write(&path, "# Title 3\n")?;
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(modified + std::time::Duration::from_secs(1))?;
+        assert_eq!(
+            html_cache.get_or_render(&config, &page_id, |_| vec![])?,
+            "<h1>Title 3</h1>\n"
+        );
+
+        // not cached if invalidated while rendering
+        html_cache.clear();
+        std::fs::write(&path, "[[Title]]\n")?;
+        let html = html_cache.get_or_render(&config, &page_id, |_| {
+            html_cache.clear();
+            vec![]
+        })?;
+        assert!(html_cache.entries().pages.is_empty());
+        assert_eq!(
+            html_cache.get_or_render(&config, &page_id, |_| vec![])?,
+            html
+        );
+        assert_eq!(html_cache.entries().pages.len(), 1);
+
+        html_cache.clear();
+        std::fs::remove_file(&path)?;
+        assert!(
+            html_cache
+                .get_or_render(&config, &page_id, |_| vec![])
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::Context as _;
+
+/// The quiet period after the last event before the changed pages are reloaded
+const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(200);
+/// The delay before restarting the watcher after it stopped
+const RESTART_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Watches the data dir in a new thread and keeps the index up to date.
+///
+/// If the watcher stops, it is restarted and the index is rebuilt by a full rescan because
+/// events may have been missed in the meantime.
+pub(super) fn spawn(state: std::sync::Arc<super::State>) {
+    std::thread::spawn(move || {
+        let watch_dir = state.config.data_dir().to_path_buf();
+        loop {
+            if let Err(e) = watch(&state, &watch_dir) {
+                eprintln!("watcher: {e:#}");
+            }
+            std::thread::sleep(RESTART_DELAY);
+            if let Err(e) = rescan(&state) {
+                eprintln!("watcher: rescan: {e:#}");
+            }
+        }
+    });
+}
+
+fn watch(state: &std::sync::Arc<super::State>, watch_dir: &std::path::Path) -> anyhow::Result<()> {
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx).context("create watcher")?;
+    // pages are placed directly in the data dir (images are in a subdirectory)
+    notify::Watcher::watch(&mut watcher, watch_dir, notify::RecursiveMode::NonRecursive)
+        .context("watch dir")?;
+
+    let mut pending = std::collections::BTreeSet::new();
+    loop {
+        let res = if pending.is_empty() {
+            rx.recv()
+                .map_err(|_| anyhow::anyhow!("watcher disconnected"))?
+        } else {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(res) => res,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                    reload_pages(state, std::mem::take(&mut pending));
+                    continue;
+                }
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                    anyhow::bail!("watcher disconnected")
+                }
+            }
+        };
+        match res {
+            Ok(event) => match event.kind {
+                notify::EventKind::Access(_) | notify::EventKind::Other => {
+                    // do nothing
+                }
+                // renames are reported as modifications of both the old and the new path
+                notify::EventKind::Any
+                | notify::EventKind::Create(_)
+                | notify::EventKind::Modify(_)
+                | notify::EventKind::Remove(_) => {
+                    pending.extend(event.paths.iter().filter_map(|path| page_id(path)));
+                }
+            },
+            Err(e) => eprintln!("watcher: {e}"),
+        }
+    }
+}
+
+/// Returns the page ID if `path` is a page file. Other files (images, editor swap files, ...)
+/// are ignored.
+fn page_id(path: &std::path::Path) -> Option<crate::page_id::PageId> {
+    if path.extension()? != "md" {
+        return None;
+    }
+    crate::page_io::PageIo::page_id(path).ok()
+}
+
+fn reload_pages(
+    state: &std::sync::Arc<super::State>,
+    page_ids: std::collections::BTreeSet<crate::page_id::PageId>,
+) {
+    if let Err(e) = state.reload_pages(&page_ids) {
+        eprintln!("watcher: {e:#}");
+    }
+}
+
+fn rescan(state: &std::sync::Arc<super::State>) -> anyhow::Result<()> {
+    let index = crate::index::Index::new(state.config.clone())?;
+    state.replace_index(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_page_id() -> anyhow::Result<()> {
+        let data_dir = std::path::Path::new("/path/to/data");
+        assert_eq!(
+            page_id(&data_dir.join("20251224T000000Z.md")),
+            Some(crate::page_id::PageId::from_str("20251224T000000Z")?)
+        );
+        assert_eq!(
+            page_id(&data_dir.join("README.md")),
+            Some(crate::page_id::PageId::root())
+        );
+        assert_eq!(page_id(&data_dir.join("images/foo.png")), None);
+        assert_eq!(page_id(&data_dir.join(".20251224T000000Z.md.swp")), None);
+        assert_eq!(page_id(&data_dir.join("20251224T000000Z.md~")), None);
+        assert_eq!(page_id(&data_dir.join("4913")), None);
+        assert_eq!(page_id(&data_dir.join("notes.md")), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+#[derive(clap::Args)]
+pub(crate) struct Args {
+    /// List the pages with the tag instead of the tags
+    tag: Option<String>,
+    /// The output format
+    #[arg(default_value = "table", long, value_enum)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum Format {
+    Json,
+    Table,
+    Tsv,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct TagRow {
+    tag: String,
+    pages: usize,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct PageRow {
+    id: String,
+    title: String,
+}
+
+pub(super) async fn execute(Args { tag, format }: Args) -> anyhow::Result<()> {
+    let config = crate::config::Config::load().await?;
+    let index = crate::index::Index::new_without_terms(config)?;
+    let mut stdout = std::io::stdout().lock();
+    match tag {
+        None => write_tag_rows(&mut stdout, format, &read_tag_rows(&index))?,
+        Some(tag) => {
+            let tag = tag.trim_start_matches('#');
+            let rows = read_page_rows(&index, tag)
+                .ok_or_else(|| anyhow::anyhow!("tag not found: {tag}"))?;
+            write_page_rows(&mut stdout, format, &rows)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_tag_rows(index: &crate::index::Index) -> Vec<TagRow> {
+    index
+        .tags
+        .iter()
+        .map(|(tag, page_ids)| TagRow {
+            tag: tag.to_owned(),
+            pages: page_ids.len(),
+        })
+        .collect::<Vec<TagRow>>()
+}
+
+fn read_page_rows(index: &crate::index::Index, tag: &str) -> Option<Vec<PageRow>> {
+    let page_ids = index.tags.get(tag)?;
+    Some(
+        page_ids
+            .iter()
+            .map(|page_id| PageRow {
+                id: page_id.to_string(),
+                title: index
+                    .page_metas
+                    .get(page_id)
+                    .and_then(|page_meta| page_meta.title.clone())
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<PageRow>>(),
+    )
+}
+
+fn write_tag_rows<W: std::io::Write>(
+    w: &mut W,
+    format: Format,
+    rows: &[TagRow],
+) -> anyhow::Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer(&mut *w, rows)?;
+            writeln!(w)?;
+        }
+        Format::Table => {
+            writeln!(w, "{:>5}  TAG", "PAGES")?;
+            for TagRow { tag, pages } in rows {
+                writeln!(w, "{pages:>5}  {tag}")?;
+            }
+        }
+        Format::Tsv => {
+            for TagRow { tag, pages } in rows {
+                writeln!(w, "{tag}\t{pages}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_page_rows<W: std::io::Write>(
+    w: &mut W,
+    format: Format,
+    rows: &[PageRow],
+) -> anyhow::Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer(&mut *w, rows)?;
+            writeln!(w)?;
+        }
+        Format::Table => {
+            writeln!(w, "{:<16}  TITLE", "ID")?;
+            for PageRow { id, title } in rows {
+                writeln!(w, "{id:<16}  {title}")?;
+            }
+        }
+        Format::Tsv => {
+            for PageRow { id, title } in rows {
+                writeln!(w, "{id}\t{}", title.replace('\t', " "))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_rows() -> anyhow::Result<()> {
+        let (_temp_dir, config) = crate::test_util::temp_data_dir()?;
+        let data_dir = config.data_dir();
+        std::fs::write(data_dir.join("20251224T000000Z.md"), "# Page 1\n\n#rust\n")?;
+        std::fs::write(
+            data_dir.join("20251224T000001Z.md"),
+            "---\ntags: [cli, rust]\n---\n# Page 2\n",
+        )?;
+        let index = crate::index::Index::new_without_terms(config)?;
+
+        assert_eq!(
+            read_tag_rows(&index),
+            vec![
+                TagRow {
+                    tag: "cli".to_owned(),
+                    pages: 1,
+                },
+                TagRow {
+                    tag: "rust".to_owned(),
+                    pages: 2,
+                },
+            ]
+        );
+        assert_eq!(
+            read_page_rows(&index, "cli"),
+            Some(vec![PageRow {
+                id: "20251224T000001Z".to_owned(),
+                title: "Page 2".to_owned(),
+            }])
+        );
+        assert_eq!(read_page_rows(&index, "unknown"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_rows() -> anyhow::Result<()> {
+        let rows = vec![TagRow {
+            tag: "rust".to_owned(),
+            pages: 2,
+        }];
+        let mut buf = vec![];
+        write_tag_rows(&mut buf, Format::Table, &rows)?;
+        assert_eq!(String::from_utf8(buf)?, "PAGES  TAG\n    2  rust\n");
+        let mut buf = vec![];
+        write_tag_rows(&mut buf, Format::Tsv, &rows)?;
+        assert_eq!(String::from_utf8(buf)?, "rust\t2\n");
+
+        let rows = vec![PageRow {
+            id: "20251224T000000Z".to_owned(),
+            title: "Title".to_owned(),
+        }];
+        let mut buf = vec![];
+        write_page_rows(&mut buf, Format::Json, &rows)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            "[{\"id\":\"20251224T000000Z\",\"title\":\"Title\"}]\n"
+        );
+        let mut buf = vec![];
+        write_page_rows(&mut buf, Format::Table, &rows)?;
+        assert_eq!(
+            String::from_utf8(buf)?,
+            "ID                TITLE\n20251224T000000Z  Title\n"
+        );
+        Ok(())
+    }
+}
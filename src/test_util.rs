@@ -0,0 +1,31 @@
+/// Creates an empty data dir in a temporary directory and the default config for it.
+///
+/// The data dir is removed when the returned [`tempfile::TempDir`] is dropped.
+pub(crate) fn temp_data_dir() -> anyhow::Result<(tempfile::TempDir, crate::config::Config)> {
+    temp_data_dir_with(serde_json::json!({}))
+}
+
+/// Like [`temp_data_dir`], but with the other config entries in the JSON object `config`.
+pub(crate) fn temp_data_dir_with(
+    mut config: serde_json::Value,
+) -> anyhow::Result<(tempfile::TempDir, crate::config::Config)> {
+    let temp_dir = tempfile::tempdir()?;
+    let data_dir = temp_dir.path().join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    config["data_dir"] = serde_json::Value::from(data_dir.to_string_lossy());
+    let config = <crate::config::Config as std::str::FromStr>::from_str(&config.to_string())?;
+    Ok((temp_dir, config))
+}
+
+pub(crate) fn create_page(
+    data_dir: &std::path::Path,
+    page_id: &str,
+    page_content: &str,
+) -> anyhow::Result<crate::page_id::PageId> {
+    let page_id = <crate::page_id::PageId as std::str::FromStr>::from_str(page_id)?;
+    std::fs::write(
+        data_dir.join(page_id.to_string()).with_extension("md"),
+        page_content,
+    )?;
+    Ok(page_id)
+}
@@ -1,5 +1,38 @@
 use anyhow::Context as _;
 
+/// Decodes `%XX` sequences in `s`. Returns `None` if the result is not UTF-8.
+pub(crate) fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1..i + 3)) {
+            (b'%', Some(hex)) if hex.iter().all(u8::is_ascii_hexdigit) => {
+                let hex = std::str::from_utf8(hex).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Encodes all bytes in `s` except unreserved characters (RFC 3986) as `%XX`.
+pub(crate) fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect::<String>()
+}
+
 pub(crate) fn list_local_image_names(
     images_dir: &std::path::Path,
 ) -> anyhow::Result<std::collections::BTreeSet<String>> {
@@ -58,3 +91,27 @@ pub(crate) async fn list_remote_image_names(
 
     Ok(image_names)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a-Z_0.~"), Some("a-Z_0.~".to_owned()));
+        assert_eq!(percent_decode("a%20b%2fc"), Some("a b/c".to_owned()));
+        assert_eq!(
+            percent_decode("%E6%97%A5%E6%9C%AC"),
+            Some("日本".to_owned())
+        );
+        assert_eq!(percent_decode("100%"), Some("100%".to_owned()));
+        assert_eq!(percent_decode("%FF"), None);
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("a-Z_0.~"), "a-Z_0.~");
+        assert_eq!(percent_encode("a b/c"), "a%20b%2Fc");
+        assert_eq!(percent_encode("日本"), "%E6%97%A5%E6%9C%AC");
+    }
+}
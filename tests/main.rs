@@ -11,8 +11,11 @@ fn test_no_subcommand() {
                 .and(predicates::str::contains("  edit"))
                 .and(predicates::str::contains("  get"))
                 .and(predicates::str::contains("  image"))
+                .and(predicates::str::contains("  list"))
                 .and(predicates::str::contains("  new"))
+                .and(predicates::str::contains("  search"))
                 .and(predicates::str::contains("  serve"))
+                .and(predicates::str::contains("  tags"))
                 .and(predicates::str::contains("  help"))
                 .and(predicates::str::contains("Options:"))
                 .and(predicates::str::contains("  -h, --help")),